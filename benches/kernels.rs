@@ -0,0 +1,146 @@
+//! 演算カーネルのベンチマーク
+//!
+//! 計測に加えて、ループ1回あたりのヒープ確保回数を数えて表示します。
+#![allow(clippy::needless_return)]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rfm::math_traits::FromPrimitiveNumber;
+use rfm::num::{Integer, Rational};
+
+/// ヒープ確保の回数を数えるアロケータ
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        return System.alloc(layout);
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        return System.realloc(ptr, layout, new_size);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// 処理中のヒープ確保回数を数える
+fn count_allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    return ALLOCATIONS.load(Ordering::Relaxed) - before;
+}
+
+const TERMS: u128 = 1_000_000;
+
+fn sum_integers(c: &mut Criterion) {
+    let values: Vec<Integer> = (0..TERMS).map(|i| Integer::from_u128(i * 0x1234_5678_9abc_def0)).collect();
+    let operator = count_allocations(|| {
+        let mut sum = Integer::from_u128(0);
+        for value in &values {
+            sum = &sum + value;
+        }
+        black_box(sum);
+    });
+    let in_place = count_allocations(|| {
+        let mut sum = Integer::from_u128(0);
+        for value in &values {
+            sum += value;
+        }
+        black_box(sum);
+    });
+    println!("sum of {} Integers: {} allocations with `+`, {} with `+=`", TERMS, operator, in_place);
+
+    let mut group = c.benchmark_group("sum_integers");
+    group.sample_size(10);
+    group.bench_function("add", |b| {
+        b.iter(|| {
+            let mut sum = Integer::from_u128(0);
+            for value in &values {
+                sum = &sum + value;
+            }
+            sum
+        })
+    });
+    group.bench_function("add_assign", |b| b.iter(|| values.iter().sum::<Integer>()));
+    group.finish();
+}
+
+fn multiply(c: &mut Criterion) {
+    let mut group = c.benchmark_group("multiply");
+    for bits in [1_000usize, 10_000, 100_000, 1_000_000] {
+        let lhs = (Integer::from_u128(3) << bits) - Integer::from_u128(1);
+        let rhs = (Integer::from_u128(5) << bits) + Integer::from_u128(7);
+        let allocations = count_allocations(|| {
+            black_box(&lhs * &rhs);
+        });
+        println!("multiply {} bits: {} allocations", bits, allocations);
+        group.bench_function(format!("{}bits", bits), |b| b.iter(|| &lhs * &rhs));
+    }
+    group.finish();
+}
+
+fn mul_small_assign(c: &mut Criterion) {
+    let base: Integer = (Integer::from_u128(1) << 4096) - Integer::from_u128(1);
+    let allocations = count_allocations(|| {
+        let mut value = base.clone();
+        for _ in 0..1000 {
+            value *= Integer::from_u128(3);
+        }
+        black_box(value);
+    });
+    println!("1000 small multiplications: {} allocations", allocations);
+    c.bench_function("mul_small_assign", |b| {
+        b.iter(|| {
+            let mut value = base.clone();
+            for _ in 0..100 {
+                value *= Integer::from_u128(3);
+            }
+            value
+        })
+    });
+}
+
+/// 小さな値が中心の処理: 調和数の有理数での累積と、1桁に収まる整数の四則演算
+fn small_values(c: &mut Criterion) {
+    let harmonic = |terms: u128| {
+        let mut sum = Rational::from_u128(0);
+        for k in 1..=terms {
+            sum += &Rational::new(&Integer::from_u128(1), &Integer::from_u128(k));
+        }
+        sum
+    };
+    let mixed = |terms: u128| {
+        let mut acc = Integer::from_u128(1);
+        for k in 1..=terms {
+            let term = Integer::from_u128(k);
+            acc = (&acc * &term + &term) % Integer::from_u128(1_000_000_007);
+        }
+        acc
+    };
+    let allocations = count_allocations(|| {
+        black_box(harmonic(30));
+    });
+    println!("harmonic sum of 30 terms: {} allocations", allocations);
+    let allocations = count_allocations(|| {
+        black_box(mixed(10_000));
+    });
+    println!("10000 small mixed operations: {} allocations", allocations);
+
+    let mut group = c.benchmark_group("small_values");
+    group.bench_function("harmonic_30", |b| b.iter(|| harmonic(black_box(30))));
+    group.bench_function("mixed_10000", |b| b.iter(|| mixed(black_box(10_000))));
+    group.finish();
+}
+
+criterion_group!(benches, sum_integers, multiply, mul_small_assign, small_values);
+criterion_main!(benches);
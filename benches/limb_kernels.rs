@@ -0,0 +1,88 @@
+//! 基本演算カーネルのスループット計測
+//!
+//! 加算・減算・1桁乗算・積和(筆算の乗算)を、オペランドの桁数ごとに計測します。
+//! スループットはオペランド1つあたりのバイト数(積和は1桁同士の積の回数)で表示します。
+#![allow(clippy::needless_return)]
+use std::mem::size_of;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use rfm::num::{Digit, Integer, Sign};
+
+/// 計測する桁数
+const SIZES: [usize; 5] = [4, 16, 64, 256, 1024];
+
+/// 積和の計測で使う短い側の桁数(Karatsuba法に切り替わらない大きさ)
+const ADDMUL_SHORT: usize = 8;
+
+/// 擬似乱数で各桁を埋めた正の整数を作る
+fn random_integer(len: usize, seed: Digit) -> Integer {
+    let mut state: Digit = seed | 1;
+    let digits: Vec<Digit> = (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        })
+        .collect();
+    return Integer::from_number_slice(&digits, Sign::Positive);
+}
+
+fn bytes_of(len: usize) -> Throughput {
+    return Throughput::Bytes((len * size_of::<Digit>()) as u64);
+}
+
+fn add(c: &mut Criterion) {
+    let mut group = c.benchmark_group("kernel_add");
+    for len in SIZES {
+        let lhs = random_integer(len, 0x2545F491);
+        let rhs = random_integer(len, 0x9E3779B9);
+        group.throughput(bytes_of(len));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &rhs, |b, rhs| {
+            b.iter_batched_ref(|| lhs.clone(), |acc| *acc += black_box(rhs), BatchSize::SmallInput)
+        });
+    }
+    group.finish();
+}
+
+fn sub(c: &mut Criterion) {
+    let mut group = c.benchmark_group("kernel_sub");
+    for len in SIZES {
+        let lhs = random_integer(len + 1, 0x2545F491);
+        let rhs = random_integer(len, 0x9E3779B9);
+        group.throughput(bytes_of(len));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &rhs, |b, rhs| {
+            b.iter_batched_ref(|| lhs.clone(), |acc| *acc -= black_box(rhs), BatchSize::SmallInput)
+        });
+    }
+    group.finish();
+}
+
+fn mul_limb(c: &mut Criterion) {
+    let mut group = c.benchmark_group("kernel_mul_limb");
+    let multiplier = random_integer(1, 0x9E3779B9);
+    for len in SIZES {
+        let lhs = random_integer(len, 0x2545F491);
+        group.throughput(bytes_of(len));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &multiplier, |b, multiplier| {
+            b.iter_batched_ref(|| lhs.clone(), |acc| *acc *= black_box(multiplier), BatchSize::SmallInput)
+        });
+    }
+    group.finish();
+}
+
+fn addmul(c: &mut Criterion) {
+    let mut group = c.benchmark_group("kernel_addmul");
+    let short = random_integer(ADDMUL_SHORT, 0x9E3779B9);
+    for len in SIZES {
+        let long = random_integer(len, 0x2545F491);
+        group.throughput(Throughput::Elements((len * ADDMUL_SHORT) as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &long, |b, long| {
+            b.iter(|| black_box(long) * &short)
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, add, sub, mul_limb, addmul);
+criterion_main!(benches);
@@ -1,43 +1,1256 @@
-///数学でよく使われるアルゴリズムを収録したモジュール
+//!数学でよく使われるアルゴリズムを収録したモジュール
 
-
-use crate::num::Integer;
+use crate::num::{Integer, Rational, Sign};
 use crate::math_traits::*;
+use crate::parallel::{self, MaybeSync};
 
-/// 与えられた2つの数値の最小公倍数を求めます
+/// 与えられた2つの数値の最大公約数を求めます
+/// 結果は常に0以上となります。(gcd(0, 0) = 0)
 pub fn gcd(lhs:&Integer,rhs:&Integer) -> Integer{
-    if lhs == rhs {
-        return lhs.clone();
+    let mut data = lhs.abs();
+    let mut rem = rhs.abs();
+    while rem != Integer::zero() {
+        let result = data.div_rem(&rem);
+        data = rem;
+        rem = result.1;
+    }
+    return data;
+}
+
+
+/**binary splitting法で超幾何級数の部分和を求めます
+
+S = Σ_{n=start}^{end-1} a(n) · (p(start)···p(n)) / (q(start)···q(n))
+の形をした級数を、分割統治で整数演算のみを用いて求めます。
+項ごとに有理数を足し合わせる場合と比べ、途中で巨大な分数の約分を必要としません。
+# Arguments
+* start - 最初の項の番号
+* end - 最後の項の次の番号(start < endであること)
+* p - 項の比の分子を与える関数
+* q - 項の比の分母を与える関数
+* a - 各項に掛ける係数を与える関数
+# Returns
+(P, Q, T)のタプル。P = Π p(n)、Q = Π q(n)、T = S·Qとなる。
+# Panics
+start >= endの場合
+# Parallelism
+`rayon`機能が有効な場合、項数が[`parallel::splitting_threshold`]以上の区間は左右を並列に計算します。
+このとき各関数は`Sync`である必要があります。
+*/
+pub fn binary_splitting<P, Q, A>(start: u128, end: u128, p: &P, q: &Q, a: &A) -> (Integer, Integer, Integer)
+where
+    P: Fn(u128) -> Integer + MaybeSync,
+    Q: Fn(u128) -> Integer + MaybeSync,
+    A: Fn(u128) -> Integer + MaybeSync,
+{
+    assert!(start < end, "empty range is not allowed.");
+    if end - start == 1 {
+        let p_value = p(start);
+        let t_value = &a(start) * &p_value;
+        return (p_value, q(start), t_value);
     }
+    let middle = start + (end - start) / 2;
+    let left = || binary_splitting(start, middle, p, q, a);
+    let right = || binary_splitting(middle, end, p, q, a);
+    let ((p_left, q_left, t_left), (p_right, q_right, t_right)) =
+        match parallel::ENABLED && end - start >= parallel::splitting_threshold() as u128 {
+            true => parallel::join(left, right),
+            false => (left(), right()),
+        };
+    return (
+        &p_left * &p_right,
+        &q_left * &q_right,
+        &(&q_right * &t_left) + &(&p_left * &t_right),
+    );
+}
+
+/**binary splitting法で超幾何級数の部分和を有理数として求めます
+
+引数は[`binary_splitting`]と同じです。
+# Returns
+部分和Sを既約分数で表した値
+*/
+pub fn binary_splitting_sum<P, Q, A>(start: u128, end: u128, p: &P, q: &Q, a: &A) -> Rational
+where
+    P: Fn(u128) -> Integer + MaybeSync,
+    Q: Fn(u128) -> Integer + MaybeSync,
+    A: Fn(u128) -> Integer + MaybeSync,
+{
+    let (_, q_value, t_value) = binary_splitting(start, end, p, q, a);
+    return Rational::new(&t_value, &q_value);
+}
+
+/// 0以上modulus未満の剰余を求めます(modulusは正であること)
+pub(crate) fn rem_euclid(value: &Integer, modulus: &Integer) -> Integer {
+    let remain = value % modulus;
+    if remain.sign() == Sign::Negative {
+        return &remain + modulus;
+    }
+    return remain;
+}
+
+/// 2を法とする下位3bit(値を8で割った余り)
+fn low_bits(value: &Integer) -> u8 {
+    return (value.number_data[0] & 7) as u8;
+}
 
-    let mut data = match lhs > rhs {
-        true =>lhs.clone(),
-        false =>rhs.clone()
+/**拡張ユークリッドの互除法
+# Returns
+a·x + b·y = gとなる(g, x, y)のタプル。gは最大公約数(0以上)
+*/
+pub fn extended_gcd(a: &Integer, b: &Integer) -> (Integer, Integer, Integer) {
+    let (mut old_r, mut r) = (a.clone(), b.clone());
+    let (mut old_x, mut x) = (Integer::one(), Integer::zero());
+    let (mut old_y, mut y) = (Integer::zero(), Integer::one());
+    while r.sign() != Sign::Zero {
+        let (quotient, remain) = old_r.div_rem(&r);
+        (old_r, r) = (r, remain);
+        let next_x = &old_x - &(&quotient * &x);
+        (old_x, x) = (x, next_x);
+        let next_y = &old_y - &(&quotient * &y);
+        (old_y, y) = (y, next_y);
+    }
+    if old_r.sign() == Sign::Negative {
+        return (-old_r, -old_x, -old_y);
+    }
+    return (old_r, old_x, old_y);
+}
+
+/**べき剰余 base^exp mod modulus を求めます
+# Returns
+0以上modulus未満の値
+# Panics
+expが負の場合、またはmodulusが正でない場合
+*/
+pub fn mod_pow(base: &Integer, exp: &Integer, modulus: &Integer) -> Integer {
+    assert!(modulus.sign() == Sign::Positive, "modulus must be positive");
+    assert!(exp.sign() != Sign::Negative, "negative exponent");
+    let mut result = rem_euclid(&Integer::one(), modulus);
+    let mut base = rem_euclid(base, modulus);
+    //下位bitから二乗しながら掛けていく
+    for i_ in 0..exp.bit_length() {
+        if (exp >> i_ as usize).is_odd() {
+            result = &(&result * &base) % modulus;
+        }
+        base = &(&base * &base) % modulus;
+    }
+    return result;
+}
+
+/// modulusを法とする逆元を求めます(存在しない場合はNone)
+/// # Panics
+/// modulusが正でない場合
+pub fn mod_inverse(value: &Integer, modulus: &Integer) -> Option<Integer> {
+    assert!(modulus.sign() == Sign::Positive, "modulus must be positive");
+    let (g, x, _) = extended_gcd(&rem_euclid(value, modulus), modulus);
+    if g != Integer::one() {
+        return None;
+    }
+    return Some(rem_euclid(&x, modulus));
+}
+
+/**ヤコビ記号 (a/n) を求めます
+
+nが素数の場合はルジャンドル記号と一致します。
+# Returns
+-1, 0, 1のいずれか
+# Panics
+nが正の奇数でない場合
+*/
+pub fn jacobi_symbol(a: &Integer, n: &Integer) -> i8 {
+    assert!(n.sign() == Sign::Positive && n.is_odd(), "n must be a positive odd number");
+    let mut a = rem_euclid(a, n);
+    let mut n = n.clone();
+    let mut result = 1;
+    while a.sign() != Sign::Zero {
+        //(2/n) = -1 となるのは n ≡ 3, 5 (mod 8) のとき
+        let twos = a.trailing_zeros();
+        a = &a >> twos as usize;
+        if twos % 2 == 1 && matches!(low_bits(&n), 3 | 5) {
+            result = -result;
+        }
+        //平方剰余の相互法則
+        (a, n) = (n, a);
+        if low_bits(&a) & 3 == 3 && low_bits(&n) & 3 == 3 {
+            result = -result;
+        }
+        a = &a % &n;
+    }
+    if n == Integer::one() {
+        return result;
+    }
+    return 0;
+}
+
+/**素数pを法とする平方根を求めます(Tonelli–Shanksのアルゴリズム)
+# Returns
+x^2 ≡ a (mod p)となるxのうち小さい方(0以上p/2以下)。平方非剰余の場合はNone
+pが奇数の合成数の場合もNoneを返すことがあります(Someの場合は必ず平方根です)
+# Panics
+pが2以上でない場合、またはpが2より大きい偶数の場合
+*/
+pub fn sqrt_mod(a: &Integer, p: &Integer) -> Option<Integer> {
+    let two = Integer::from_u32(2);
+    assert!(*p >= two, "modulus must be a prime");
+    let a = rem_euclid(a, p);
+    if a.sign() == Sign::Zero || *p == two {
+        return Some(a);
+    }
+    if jacobi_symbol(&a, p) != 1 {
+        return None;
+    }
+    //p - 1 = q·2^s (qは奇数)
+    let p_minus_one = p - &Integer::one();
+    let s = p_minus_one.trailing_zeros();
+    let q = &p_minus_one >> s as usize;
+    let mut root = mod_pow(&a, &(&(&q + &Integer::one()) >> 1), p);
+    if s > 1 {
+        //平方非剰余zを探す
+        //pが素数なら平方非剰余はp未満に必ず存在する
+        let mut z = two.clone();
+        while jacobi_symbol(&z, p) != -1 {
+            z = &z + &Integer::one();
+            if z >= *p {
+                return None;
+            }
+        }
+        let mut m = s;
+        let mut c = mod_pow(&z, &q, p);
+        let mut t = mod_pow(&a, &q, p);
+        while t != Integer::one() {
+            //t^(2^i) = 1 となる最小のiを探す
+            let mut i_ = 0;
+            let mut square = t.clone();
+            while square != Integer::one() {
+                square = &(&square * &square) % p;
+                i_ += 1;
+                //pが素数ならi < mで見つかる
+                if i_ >= m {
+                    return None;
+                }
+            }
+            let b = mod_pow(&c, &(Integer::one() << (m - i_ - 1) as usize), p);
+            m = i_;
+            c = &(&b * &b) % p;
+            t = &(&t * &c) % p;
+            root = &(&root * &b) % p;
+        }
+    }
+    //pが合成数の場合は平方根になっていないことがある
+    if &(&root * &root) % p != a {
+        return None;
+    }
+    let other = p - &root;
+    return Some(std::cmp::min(root, other));
+}
+
+/**中国剰余定理により連立合同式 x ≡ r_i (mod m_i) を解きます
+
+法は互いに素でなくても構いません。
+# Arguments
+* congruences - (剰余, 法)の組の列
+# Returns
+解が存在する場合は(x, 法の最小公倍数)。xは0以上で最小公倍数未満。空の列に対しては(0, 1)
+解が存在しない(合同式が矛盾する)場合はNone
+# Panics
+法に正でない値が含まれる場合
+*/
+pub fn crt(congruences: &[(Integer, Integer)]) -> Option<(Integer, Integer)> {
+    let mut result = (Integer::zero(), Integer::one());
+    for (residue, modulus) in congruences {
+        assert!(modulus.sign() == Sign::Positive, "modulus must be positive");
+        let (current, lcm) = result;
+        //current + lcm·k ≡ residue (mod modulus) を満たすkを求める
+        let (g, inverse, _) = extended_gcd(&lcm, modulus);
+        let (quotient, remain) = (residue - &current).div_rem(&g);
+        if remain.sign() != Sign::Zero {
+            return None;
+        }
+        let reduced = modulus / &g;
+        let k = rem_euclid(&(&quotient * &inverse), &reduced);
+        let next_lcm = &lcm * &reduced;
+        result = (rem_euclid(&(&current + &(&lcm * &k)), &next_lcm), next_lcm);
+    }
+    return Some(result);
+}
+
+/**有理数再構成: 剰余から有理数を復元します
+
+n ≡ residue·d (mod modulus)、|n| ≤ √(modulus/2)、0 < d ≤ √(modulus/2)を満たす既約分数n/dを求めます。
+条件を満たす分数は存在すれば一意です。多倍長のモジュラー計算で得た結果から有理数の解を得るのに使えます。
+# Returns
+条件を満たす分数が存在しない場合はNone
+# Panics
+modulusが正でない場合
+*/
+pub fn rational_reconstruction(residue: &Integer, modulus: &Integer) -> Option<Rational> {
+    assert!(modulus.sign() == Sign::Positive, "modulus must be positive");
+    let bound = (modulus >> 1).sqrt();
+    //拡張ユークリッドの互除法を剰余がbound以下になるまで進める
+    let (mut old_r, mut r) = (modulus.clone(), rem_euclid(residue, modulus));
+    let (mut old_t, mut t) = (Integer::zero(), Integer::one());
+    while r > bound {
+        let (quotient, remain) = old_r.div_rem(&r);
+        (old_r, r) = (r, remain);
+        let next_t = &old_t - &(&quotient * &t);
+        (old_t, t) = (t, next_t);
+    }
+    if t.sign() == Sign::Zero || t.abs() > bound || gcd(&r, &t) != Integer::one() {
+        return None;
+    }
+    return Some(Rational::new(&r, &t));
+}
+
+/// start, start+step, ..., start+(count-1)·stepの積を分割統治で求める
+fn product_range(start: u64, count: u64, step: u64) -> Integer {
+    if count <= 16 {
+        let mut result = Integer::one();
+        for i_ in 0..count {
+            result *= Integer::from_u64(start + i_ * step);
+        }
+        return result;
+    }
+    let half = count / 2;
+    let left = || product_range(start, half, step);
+    let right = || product_range(start + half * step, count - half, step);
+    let (left, right) = match parallel::ENABLED && count >= parallel::splitting_threshold() {
+        true => parallel::join(left, right),
+        false => (left(), right()),
     };
-    let mut rem = match lhs > rhs {
-        true =>rhs.clone(),
-        false =>lhs.clone()
+    return &left * &right;
+}
+
+/// low < k ≤ highを満たす奇数kの積
+fn odd_product(low: u64, high: u64) -> Integer {
+    let first = (low + 1) | 1;
+    if first > high {
+        return Integer::one();
+    }
+    return product_range(first, (high - first) / 2 + 1, 2);
+}
+
+/**階乗 n! を求めます
+
+n!を2のべきと奇数部分に分け、奇数部分を区間ごとの積の積み重ねとして求めます。
+区間の積は分割統治で計算するため、大きなnでも高速な乗算が活かされます。
+*/
+pub fn factorial(n: u64) -> Integer {
+    //n!の奇数部分 = Π_i (n/2^(i+1), n/2^i]の奇数の積の累積
+    let mut partial = Integer::one();
+    let mut result = Integer::one();
+    for i_ in (0..u64::BITS - n.leading_zeros()).rev() {
+        partial *= odd_product(n >> (i_ + 1), n >> i_);
+        result *= &partial;
+    }
+    return result << (n - n.count_ones() as u64) as usize;
+}
+
+/// 二重階乗 n!! = n·(n-2)·(n-4)··· を求めます(0!! = 1)
+pub fn double_factorial(n: u64) -> Integer {
+    if n.is_multiple_of(2) {
+        return factorial(n / 2) << (n / 2) as usize;
+    }
+    return odd_product(0, n);
+}
+
+/// 二項係数 C(n, k) を求めます(k > nの場合は0)
+pub fn binomial(n: u64, k: u64) -> Integer {
+    if k > n {
+        return Integer::zero();
+    }
+    let k = k.min(n - k);
+    return &product_range(n - k + 1, k, 1) / &factorial(k);
+}
+
+/// 多項係数 (k_1 + k_2 + ... + k_m)! / (k_1!·k_2!···k_m!) を求めます
+pub fn multinomial(ks: &[u64]) -> Integer {
+    let mut result = Integer::one();
+    let mut total = 0;
+    for k in ks {
+        total += k;
+        result *= binomial(total, *k);
+    }
+    return result;
+}
+
+/// (F(n), F(n+1))をfast doubling法で求める
+fn fibonacci_pair(n: u64) -> (Integer, Integer) {
+    let (mut a, mut b) = (Integer::zero(), Integer::one());
+    for i_ in (0..u64::BITS - n.leading_zeros()).rev() {
+        //F(2k) = F(k)·(2F(k+1) - F(k))、F(2k+1) = F(k)^2 + F(k+1)^2
+        let doubled = &a * &(&(&b << 1) - &a);
+        let doubled_next = &(&a * &a) + &(&b * &b);
+        (a, b) = match (n >> i_) & 1 {
+            0 => (doubled, doubled_next),
+            _ => {
+                let sum = &doubled + &doubled_next;
+                (doubled_next, sum)
+            }
+        };
+    }
+    return (a, b);
+}
+
+/// フィボナッチ数 F(n) を求めます(F(0) = 0、F(1) = 1)
+pub fn fibonacci(n: u64) -> Integer {
+    return fibonacci_pair(n).0;
+}
+
+/// リュカ数 L(n) を求めます(L(0) = 2、L(1) = 1)
+pub fn lucas(n: u64) -> Integer {
+    //L(n) = 2F(n+1) - F(n)
+    let (current, next) = fibonacci_pair(n);
+    return &(&next << 1) - &current;
+}
+
+/// カタラン数 C(2n, n) / (n+1) を求めます
+pub fn catalan(n: u64) -> Integer {
+    return &binomial(2 * n, n) / &Integer::from_u64(n + 1);
+}
+
+/// 漸化式 a(m, j) = coefficient(m, j)·a(m-1, j) + a(m-1, j-1) の表のn行目をk列目まで求める
+fn stirling_row<F: Fn(u64, u64) -> u64>(n: u64, k: u64, coefficient: F) -> Vec<Integer> {
+    let mut row = vec![Integer::zero(); k as usize + 1];
+    row[0] = Integer::one();
+    for m in 1..=n {
+        for j in (1..=k.min(m)).rev() {
+            let scaled = &row[j as usize] * &Integer::from_u64(coefficient(m, j));
+            row[j as usize] = &scaled + &row[j as usize - 1];
+        }
+        row[0] = Integer::zero();
+    }
+    return row;
+}
+
+/// 第1種スターリング数(符号なし) [n, k] を求めます
+/// n個の要素をk個の巡回置換に分ける方法の数です。
+pub fn stirling_first(n: u64, k: u64) -> Integer {
+    if k > n {
+        return Integer::zero();
+    }
+    return stirling_row(n, k, |m, _| m - 1).swap_remove(k as usize);
+}
+
+/// 第2種スターリング数 {n, k} を求めます
+/// n個の要素をk個の空でない組に分ける方法の数です。
+pub fn stirling_second(n: u64, k: u64) -> Integer {
+    if k > n {
+        return Integer::zero();
+    }
+    return stirling_row(n, k, |_, j| j).swap_remove(k as usize);
+}
+
+/// ベル数 B(n) を求めます
+/// n個の要素を空でない組に分ける方法の総数です。
+pub fn bell(n: u64) -> Integer {
+    //ベルの三角形: 各行の先頭は前の行の末尾、以降は左と左上の和
+    let mut row = vec![Integer::one()];
+    for _ in 0..n {
+        let mut next = Vec::with_capacity(row.len() + 1);
+        next.push(row[row.len() - 1].clone());
+        for value in &row {
+            let sum = &next[next.len() - 1] + value;
+            next.push(sum);
+        }
+        row = next;
+    }
+    return row.swap_remove(0);
+}
+
+/// 試し割りに使う小さな素数
+const SMALL_PRIMES: [u64; 25] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97];
+
+/**ミラー–ラビン法による素数判定
+
+最初の13個の素数を底として判定するため、3.3·10^24未満では結果は確定的です。
+それ以上の値では合成数を素数と誤判定する可能性がわずかに残ります。
+*/
+pub fn is_probable_prime(n: &Integer) -> bool {
+    if *n < Integer::from_u32(2) {
+        return false;
+    }
+    for prime in SMALL_PRIMES {
+        let prime = Integer::from_u64(prime);
+        if *n == prime {
+            return true;
+        }
+        if (n % &prime).sign() == Sign::Zero {
+            return false;
+        }
+    }
+    let one = Integer::one();
+    let n_minus_one = n - &one;
+    let s = n_minus_one.trailing_zeros();
+    let d = &n_minus_one >> s as usize;
+    for base in &SMALL_PRIMES[..13] {
+        let mut x = mod_pow(&Integer::from_u64(*base), &d, n);
+        if x == one || x == n_minus_one {
+            continue;
+        }
+        let mut witness = true;
+        for _ in 1..s {
+            x = &(&x * &x) % n;
+            if x == n_minus_one {
+                witness = false;
+                break;
+            }
+        }
+        if witness {
+            return false;
+        }
+    }
+    return true;
+}
+
+/// 合成数nの自明でない約数をポラードのρ法(Brentの改良版)で求める
+fn pollard_rho(n: &Integer) -> Integer {
+    //gcdをまとめて取る間隔
+    const BATCH: u64 = 128;
+    let one = Integer::one();
+    let mut c = Integer::one();
+    loop {
+        let f = |x: &Integer| &(&(x * x) + &c) % n;
+        let (mut x, mut y, mut saved) = (Integer::zero(), Integer::from_u32(2), Integer::zero());
+        let (mut product, mut divisor) = (one.clone(), one.clone());
+        let mut length = 1;
+        while divisor == one {
+            x = y.clone();
+            for _ in 0..length {
+                y = f(&y);
+            }
+            let mut k = 0;
+            while k < length && divisor == one {
+                saved = y.clone();
+                for _ in 0..BATCH.min(length - k) {
+                    y = f(&y);
+                    product = &(&product * &(&x - &y).abs()) % n;
+                }
+                divisor = gcd(&product, n);
+                k += BATCH;
+            }
+            length *= 2;
+        }
+        if divisor == *n {
+            //まとめたことで行き過ぎた場合は1歩ずつやり直す
+            loop {
+                saved = f(&saved);
+                divisor = gcd(&(&x - &saved), n);
+                if divisor != one {
+                    break;
+                }
+            }
+        }
+        if divisor != *n {
+            return divisor;
+        }
+        c = &c + &one;
+    }
+}
+
+fn collect_prime_factors(n: Integer, factors: &mut Vec<Integer>) {
+    if n == Integer::one() {
+        return;
+    }
+    if is_probable_prime(&n) {
+        factors.push(n);
+        return;
+    }
+    let divisor = pollard_rho(&n);
+    let cofactor = &n / &divisor;
+    collect_prime_factors(divisor, factors);
+    collect_prime_factors(cofactor, factors);
+}
+
+/**素因数分解を行います
+
+小さな素数による試し割りの後、ポラードのρ法で分解します。
+# Returns
+(素因数, 指数)の組を素因数の昇順に並べたもの。負の数は絶対値を分解し、1に対しては空
+# Panics
+nが0の場合
+*/
+pub fn factorize(n: &Integer) -> Vec<(Integer, u32)> {
+    assert!(n.sign() != Sign::Zero, "cannot factorize zero");
+    let mut rest = n.abs();
+    let mut primes = Vec::new();
+    for prime in SMALL_PRIMES {
+        let prime = Integer::from_u64(prime);
+        loop {
+            let (quotient, remain) = rest.div_rem(&prime);
+            if remain.sign() != Sign::Zero {
+                break;
+            }
+            primes.push(prime.clone());
+            rest = quotient;
+        }
+    }
+    collect_prime_factors(rest, &mut primes);
+    primes.sort();
+    let mut result: Vec<(Integer, u32)> = Vec::new();
+    for prime in primes {
+        match result.last_mut() {
+            Some((last, exponent)) if *last == prime => *exponent += 1,
+            _ => result.push((prime, 1)),
+        }
+    }
+    return result;
+}
+
+/// オイラーのφ関数(n以下でnと互いに素な正の整数の個数)を求めます
+/// # Panics
+/// nが正でない場合
+pub fn euler_phi(n: &Integer) -> Integer {
+    assert!(n.sign() == Sign::Positive, "n must be positive");
+    let mut result = n.clone();
+    for (prime, _) in factorize(n) {
+        result = &(&result / &prime) * &(&prime - &Integer::one());
+    }
+    return result;
+}
+
+/// 正の約数を昇順に列挙します(負の数は絶対値の約数)
+/// # Panics
+/// nが0の場合
+pub fn divisors(n: &Integer) -> Vec<Integer> {
+    let mut result = vec![Integer::one()];
+    for (prime, exponent) in factorize(n) {
+        let mut next = Vec::with_capacity(result.len() * (exponent as usize + 1));
+        for divisor in &result {
+            let mut value = divisor.clone();
+            next.push(value.clone());
+            for _ in 0..exponent {
+                value = &value * &prime;
+                next.push(value.clone());
+            }
+        }
+        result = next;
+    }
+    result.sort();
+    return result;
+}
+
+/// 約数関数 σ_k(n)(正の約数のk乗の和)を求めます
+/// # Panics
+/// nが正でない場合
+pub fn sigma_k(n: &Integer, k: u32) -> Integer {
+    assert!(n.sign() == Sign::Positive, "n must be positive");
+    let mut result = Integer::one();
+    for (prime, exponent) in factorize(n) {
+        //1 + p^k + p^2k + ... + p^ek
+        let power = prime.pow(Integer::from_u32(k));
+        let mut term = Integer::one();
+        let mut sum = Integer::one();
+        for _ in 0..exponent {
+            term = &term * &power;
+            sum = &sum + &term;
+        }
+        result = &result * &sum;
+    }
+    return result;
+}
+
+/// メビウス関数 μ(n) を求めます
+/// # Returns
+/// 平方因子を持つ場合は0、それ以外は素因数の個数が偶数なら1、奇数なら-1
+/// # Panics
+/// nが正でない場合
+pub fn mobius(n: &Integer) -> i8 {
+    assert!(n.sign() == Sign::Positive, "n must be positive");
+    let factors = factorize(n);
+    if factors.iter().any(|(_, exponent)| *exponent > 1) {
+        return 0;
+    }
+    return match factors.len() % 2 {
+        0 => 1,
+        _ => -1,
+    };
+}
+
+/**クロネッカー記号 (a/n) を求めます
+
+ヤコビ記号を任意の整数nに拡張したものです。
+# Returns
+-1, 0, 1のいずれか
+*/
+pub fn kronecker_symbol(a: &Integer, n: &Integer) -> i8 {
+    if n.sign() == Sign::Zero {
+        return match a.abs() == Integer::one() {
+            true => 1,
+            false => 0,
+        };
+    }
+    let mut result = 1;
+    if n.sign() == Sign::Negative && a.sign() == Sign::Negative {
+        result = -1;
+    }
+    let twos = n.trailing_zeros();
+    let odd = &n.abs() >> twos as usize;
+    if twos > 0 {
+        //(a/2) = 0 (aが偶数)、1 (a ≡ ±1 mod 8)、-1 (a ≡ ±3 mod 8)
+        let residue = low_bits(&rem_euclid(a, &Integer::from_u32(8)));
+        if residue.is_multiple_of(2) {
+            return 0;
+        }
+        if twos % 2 == 1 && matches!(residue, 3 | 5) {
+            result = -result;
+        }
+    }
+    return result * jacobi_symbol(a, &odd);
+}
+
+/// gの位数を求める(orderはgの位数の倍数であること)
+/// orderをその素因数で割っても g^order ≡ 1 が保たれる限り割り続ける。
+fn multiplicative_order(g: &Integer, modulus: &Integer, order: &Integer) -> Integer {
+    let one = Integer::one();
+    let mut result = order.clone();
+    for (prime, _) in factorize(order) {
+        while (&result % &prime).sign() == Sign::Zero && mod_pow(g, &(&result / &prime), modulus) == one {
+            result = &result / &prime;
+        }
+    }
+    return result;
+}
+
+/**最小の原始根を求めます
+
+原始根が存在するのは法が1, 2, 4, p^k, 2p^k(pは奇素数)の場合のみです。
+# Returns
+原始根が存在しない場合はNone
+# Panics
+modulusが正でない場合
+*/
+pub fn primitive_root(modulus: &Integer) -> Option<Integer> {
+    assert!(modulus.sign() == Sign::Positive, "modulus must be positive");
+    if *modulus <= Integer::from_u32(4) {
+        return match modulus.to_i128() {
+            Some(1) => Some(Integer::zero()),
+            Some(3) => Some(Integer::from_u32(2)),
+            Some(4) => Some(Integer::from_u32(3)),
+            _ => Some(Integer::one()),
+        };
+    }
+    let odd_part = match modulus.is_even() {
+        true => modulus >> 1,
+        false => modulus.clone(),
     };
+    let factors = factorize(&odd_part);
+    if factors.len() != 1 || odd_part.is_even() {
+        return None;
+    }
+    let phi = euler_phi(modulus);
+    let phi_primes: Vec<Integer> = factorize(&phi).into_iter().map(|(prime, _)| prime).collect();
+    let one = Integer::one();
+    let mut candidate = Integer::from_u32(2);
     loop {
-        let result = data.div_rem(&rem);
-        if result.1 == Integer::zero() {
-            return rem;
+        if gcd(&candidate, modulus) == one
+            && phi_primes.iter().all(|prime| mod_pow(&candidate, &(&phi / prime), modulus) != one)
+        {
+            return Some(candidate);
         }
-        data = rem;
-        rem = result.1;
+        candidate = &candidate + &one;
+    }
+}
+
+/// 位数orderの元gについて g^x ≡ h となる0 ≤ x < orderをBaby-step Giant-step法で求める
+fn baby_step_giant_step(g: &Integer, h: &Integer, order: &Integer, modulus: &Integer) -> Option<Integer> {
+    let step = &(order - &Integer::one()).sqrt() + &Integer::one();
+    //baby step: (g^j, j)を値でソートして二分探索する
+    let mut table: Vec<(Integer, Integer)> = Vec::new();
+    let mut value = Integer::one();
+    let mut j = Integer::zero();
+    while j < step {
+        table.push((value.clone(), j.clone()));
+        value = &(&value * g) % modulus;
+        j = &j + &Integer::one();
+    }
+    table.sort();
+    //giant step: h·(g^-step)^i
+    let factor = mod_inverse(&mod_pow(g, &step, modulus), modulus)?;
+    let mut current = h.clone();
+    let mut i_ = Integer::zero();
+    while i_ < step {
+        if let Ok(position) = table.binary_search_by(|(value, _)| value.cmp(&current)) {
+            let x = &(&i_ * &step) + &table[position].1;
+            if x < *order {
+                return Some(x);
+            }
+        }
+        current = &(&current * &factor) % modulus;
+        i_ = &i_ + &Integer::one();
+    }
+    return None;
+}
+
+/**離散対数: g^x ≡ h (mod modulus)となる最小の0以上の整数xを求めます
+
+gの位数を素因数分解し、素数べきごとにPohlig–Hellman法で分解してからBaby-step Giant-step法で解き、
+中国剰余定理で結合します。計算量はgの位数の最大素因数の平方根に比例します。
+# Returns
+解が存在しない場合はNone
+# Panics
+modulusが正でない場合、またはgとmodulusが互いに素でない場合
+*/
+pub fn discrete_log(g: &Integer, h: &Integer, modulus: &Integer) -> Option<Integer> {
+    assert!(modulus.sign() == Sign::Positive, "modulus must be positive");
+    let one = Integer::one();
+    let g = rem_euclid(g, modulus);
+    let h = rem_euclid(h, modulus);
+    if *modulus == one {
+        return Some(Integer::zero());
+    }
+    assert!(gcd(&g, modulus) == one, "base must be coprime to modulus");
+    let order = multiplicative_order(&g, modulus, &euler_phi(modulus));
+    let mut congruences = Vec::new();
+    for (prime, exponent) in factorize(&order) {
+        //位数prime^exponentの部分群に射影し、prime進数の桁を1つずつ求める
+        let prime_power = prime.pow(Integer::from_u32(exponent));
+        let cofactor = &order / &prime_power;
+        let g_part = mod_pow(&g, &cofactor, modulus);
+        let h_part = mod_pow(&h, &cofactor, modulus);
+        let generator = mod_pow(&g_part, &(&prime_power / &prime), modulus);
+        let g_inverse = mod_inverse(&g_part, modulus)?;
+        let mut x = Integer::zero();
+        let mut place = Integer::one();
+        for k in 0..exponent {
+            let reduced = &(&mod_pow(&g_inverse, &x, modulus) * &h_part) % modulus;
+            let target = mod_pow(&reduced, &prime.pow(Integer::from_u32(exponent - 1 - k)), modulus);
+            let digit = baby_step_giant_step(&generator, &target, &prime, modulus)?;
+            x = &x + &(&digit * &place);
+            place = &place * &prime;
+        }
+        congruences.push((x, prime_power));
     }
+    let (x, _) = crt(&congruences)?;
+    //hがgの生成する部分群に含まれない場合は射影した問題が解けても元の問題は解けない
+    if mod_pow(&g, &x, modulus) != h {
+        return None;
+    }
+    return Some(x);
+}
+
+/**ベルヌーイ数 B_0, B_1, ..., B_n をまとめて求めます
+
+B_1 = -1/2 とする流儀です。奇数番目はB_1を除いて0です。
+偶数番目は正接数(tan xのテイラー係数)を整数の漸化式で求めてから変換するため、
+途中で有理数の約分を繰り返す必要がありません。
+*/
+pub fn bernoulli_numbers(n: u64) -> Vec<Rational> {
+    let half = (n / 2) as usize;
+    //tangent[k - 1] = T_(2k-1)
+    let mut tangent = vec![Integer::zero(); half];
+    if half > 0 {
+        tangent[0] = Integer::one();
+    }
+    for k in 1..half {
+        tangent[k] = &tangent[k - 1] * &Integer::from_u64(k as u64);
+    }
+    for k in 1..half {
+        for j in k..half {
+            tangent[j] = &(&tangent[j - 1] * &Integer::from_u64((j - k) as u64)) + &(&tangent[j] * &Integer::from_u64((j - k + 2) as u64));
+        }
+    }
+    let mut result = Vec::with_capacity(n as usize + 1);
+    result.push(Rational::one());
+    for m in 1..=n {
+        if m == 1 {
+            result.push(Rational::new(&Integer::from_i32(-1), &Integer::from_u32(2)));
+            continue;
+        }
+        if m % 2 == 1 {
+            result.push(Rational::zero());
+            continue;
+        }
+        //B_2k = (-1)^(k-1)·2k·T_(2k-1) / (4^k·(4^k - 1))
+        let k = m / 2;
+        let power = Integer::one() << m as usize;
+        let numerator = &tangent[k as usize - 1] * &Integer::from_u64(m);
+        let value = Rational::new(&numerator, &(&power * &(&power - &Integer::one())));
+        result.push(match k % 2 {
+            0 => -value,
+            _ => value,
+        });
+    }
+    return result;
+}
+
+/// ベルヌーイ数 B_n を求めます(B_1 = -1/2)
+pub fn bernoulli(n: u64) -> Rational {
+    if n > 1 && n % 2 == 1 {
+        return Rational::zero();
+    }
+    return bernoulli_numbers(n).swap_remove(n as usize);
+}
+
+/**オイラー数 E_0, E_1, ..., E_n をまとめて求めます
+
+sech xのテイラー係数から定まる整数列で、E_0 = 1、E_2 = -1、E_4 = 5 のように符号が交互に変わります。
+奇数番目は0です。正割数を整数の漸化式で求めてから符号を付けます。
+*/
+pub fn euler_numbers(n: u64) -> Vec<Integer> {
+    let half = (n / 2) as usize;
+    //secant[k] = |E_2k|
+    let mut secant = vec![Integer::one(); half + 1];
+    for k in 1..=half {
+        secant[k] = &secant[k - 1] * &Integer::from_u64(k as u64);
+    }
+    for k in 1..=half {
+        for j in k + 1..=half {
+            secant[j] = &(&secant[j - 1] * &Integer::from_u64((j - k) as u64)) + &(&secant[j] * &Integer::from_u64((j - k + 1) as u64));
+        }
+    }
+    return (0..=n)
+        .map(|m| match (m % 2, (m / 2) % 2) {
+            (1, _) => Integer::zero(),
+            (_, 0) => secant[(m / 2) as usize].clone(),
+            _ => -&secant[(m / 2) as usize],
+        })
+        .collect();
+}
+
+/// オイラー数 E_n を求めます
+pub fn euler_number(n: u64) -> Integer {
+    if n % 2 == 1 {
+        return Integer::zero();
+    }
+    return euler_numbers(n).swap_remove(n as usize);
+}
+
+/// Σ_{k=start}^{end-1} 1/k^s を(分子, 分母)の組として分割統治で求める
+fn power_reciprocal_sum(start: u64, end: u64, s: u32) -> (Integer, Integer) {
+    if end - start == 1 {
+        return (Integer::one(), Integer::from_u64(start).pow(Integer::from_u32(s)));
+    }
+    let middle = start + (end - start) / 2;
+    let left = || power_reciprocal_sum(start, middle, s);
+    let right = || power_reciprocal_sum(middle, end, s);
+    let ((left_numerator, left_denominator), (right_numerator, right_denominator)) =
+        match parallel::ENABLED && end - start >= parallel::splitting_threshold() {
+            true => parallel::join(left, right),
+            false => (left(), right()),
+        };
+    return (
+        &(&left_numerator * &right_denominator) + &(&right_numerator * &left_denominator),
+        &left_denominator * &right_denominator,
+    );
 }
 
+/**ゼータ関数の部分和 Σ_{k=1}^{n} 1/k^s を既約分数で求めます
+
+項を分割統治でまとめ、最後に1回だけ約分します。
+# Panics
+sが0の場合
+*/
+pub fn zeta_partial_sum(s: u32, n: u64) -> Rational {
+    assert!(s > 0, "s must be positive");
+    if n == 0 {
+        return Rational::zero();
+    }
+    let (numerator, denominator) = power_reciprocal_sum(1, n + 1, s);
+    return Rational::new(&numerator, &denominator);
+}
+
+/// 調和数 H(n) = 1 + 1/2 + ... + 1/n を求めます(H(0) = 0)
+pub fn harmonic(n: u64) -> Rational {
+    return zeta_partial_sum(1, n);
+}
+
+/**偶数でのゼータ関数の値 ζ(2m) = r·π^(2m) を満たす有理数rを求めます
+
+r = (-1)^(m+1)·B_2m·2^(2m-1) / (2m)! です。
+# Panics
+mが0の場合
+*/
+pub fn zeta_even_coefficient(m: u64) -> Rational {
+    assert!(m > 0, "m must be positive");
+    let value = &bernoulli(2 * m).abs() * &Rational::from(&(Integer::one() << (2 * m - 1) as usize));
+    return &value / &Rational::from(&factorial(2 * m));
+}
 
 #[cfg(test)]
 mod integer_test {
-    use crate::{num::Integer, math_traits::FromPrimitiveNumber};
-    use super::gcd;
+    use crate::{num::{Integer, Rational}, math_traits::{FromPrimitiveNumber, One}};
+    use super::*;
 
 
     #[test]
     fn test_gcd(){
         assert_eq!(gcd(&Integer::from_i128(10),&Integer::from_i128(8)),Integer::from_i128(2));
+        assert_eq!(gcd(&Integer::from_i128(-12),&Integer::from_i128(18)),Integer::from_i128(6));
+        assert_eq!(gcd(&Integer::from_i128(0),&Integer::from_i128(7)),Integer::from_i128(7));
+    }
+
+    #[test]
+    fn test_extended_gcd(){
+        let (a, b) = (Integer::from_i128(240), Integer::from_i128(-46));
+        let (g, x, y) = extended_gcd(&a, &b);
+        assert_eq!(g, Integer::from_i128(2));
+        assert_eq!(&(&a * &x) + &(&b * &y), g);
+        assert_eq!(extended_gcd(&Integer::from_i128(0), &Integer::from_i128(-5)).0, Integer::from_i128(5));
+    }
+
+    #[test]
+    fn test_mod_pow_inverse(){
+        let m = Integer::from_i128(1_000_000_007);
+        assert_eq!(mod_pow(&Integer::from_i128(2), &Integer::from_i128(1_000_000_006), &m), Integer::from_i128(1));
+        assert_eq!(mod_pow(&Integer::from_i128(-2), &Integer::from_i128(3), &Integer::from_i128(5)), Integer::from_i128(2));
+        assert_eq!(mod_pow(&Integer::from_i128(7), &Integer::from_i128(0), &Integer::from_i128(1)), Integer::from_i128(0));
+        assert_eq!(mod_inverse(&Integer::from_i128(-3), &Integer::from_i128(7)), Some(Integer::from_i128(2)));
+        assert_eq!(mod_inverse(&Integer::from_i128(6), &Integer::from_i128(9)), None);
+    }
+
+    #[test]
+    fn test_jacobi_symbol(){
+        //(a/n)の値をPythonで求めた結果と比較する
+        let expected = [(1001, 9907, -1), (19, 45, 1), (8, 21, -1), (5, 21, 1), (-1, 7, -1), (-1, 13, 1), (30, 1, 1), (6, 15, 0)];
+        for (a, n, symbol) in expected {
+            assert_eq!(jacobi_symbol(&Integer::from_i128(a), &Integer::from_i128(n)), symbol, "({}/{})", a, n);
+        }
+    }
+
+    #[test]
+    fn test_sqrt_mod(){
+        let p = Integer::from_i128(1_000_000_009);
+        let root = sqrt_mod(&Integer::from_i128(4), &p).unwrap();
+        assert_eq!(root, Integer::from_i128(2));
+        assert_eq!(sqrt_mod(&Integer::from_i128(2), &Integer::from_i128(7)), Some(Integer::from_i128(3)));
+        assert_eq!(sqrt_mod(&Integer::from_i128(3), &Integer::from_i128(7)), None);
+        assert_eq!(sqrt_mod(&Integer::from_i128(1), &Integer::from_i128(2)), Some(Integer::from_i128(1)));
+        //p - 1 = 25·2^64 の素数
+        let p: Integer = "461168601842738790401".parse().unwrap();
+        let square = mod_pow(&Integer::from_i128(3), &Integer::from_i128(100), &p);
+        let root = sqrt_mod(&square, &p).unwrap();
+        assert_eq!(&(&root * &root) % &p, square);
+    }
+
+    #[test]
+    fn test_sqrt_mod_composite(){
+        //素数でない法でも停止し、Someの場合は平方根になっている
+        assert_eq!(sqrt_mod(&Integer::from_i128(4), &Integer::from_i128(9)), None);
+        for n in [9, 15, 21, 25, 33, 45, 49, 65, 105] {
+            let n = Integer::from_i128(n);
+            let mut a = Integer::zero();
+            while a < n {
+                if let Some(root) = sqrt_mod(&a, &n) {
+                    assert_eq!(&(&root * &root) % &n, a, "sqrt({}) mod {}", a, n);
+                }
+                a = &a + &Integer::one();
+            }
+        }
+    }
+
+    #[test]
+    fn test_crt(){
+        let congruences = |values: &[(i128, i128)]| -> Vec<(Integer, Integer)> {
+            values.iter().map(|(r, m)| (Integer::from_i128(*r), Integer::from_i128(*m))).collect()
+        };
+        let solved = |x: i128, m: i128| Some((Integer::from_i128(x), Integer::from_i128(m)));
+        assert_eq!(crt(&congruences(&[(2, 3), (3, 5), (2, 7)])), solved(23, 105));
+        assert_eq!(crt(&congruences(&[(-1, 4), (5, 6)])), solved(11, 12));
+        assert_eq!(crt(&congruences(&[(1, 4), (2, 6)])), None);
+        assert_eq!(crt(&congruences(&[(3, 10), (13, 10)])), solved(3, 10));
+        assert_eq!(crt(&[]), solved(0, 1));
+        //2^127 - 1 と 2^89 - 1 はどちらも素数
+        let m1 = (Integer::one() << 127) - Integer::one();
+        let m2 = (Integer::one() << 89) - Integer::one();
+        let x: Integer = "123456789012345678901234567890123456789012345678901234567".parse().unwrap();
+        let (solution, lcm) = crt(&[(&x % &m1, m1.clone()), (&x % &m2, m2.clone())]).unwrap();
+        assert_eq!(lcm, &m1 * &m2);
+        assert_eq!(solution, x);
+    }
+
+    #[test]
+    fn test_rational_reconstruction(){
+        let m = Integer::from_i128(1_000_000_007);
+        for value in ["-3/7", "22/7", "0", "-12345/101"] {
+            let value: Rational = value.parse().unwrap();
+            let residue = rem_euclid(&(value.numer() * &mod_inverse(value.denom(), &m).unwrap()), &m);
+            assert_eq!(rational_reconstruction(&residue, &m), Some(value));
+        }
+        assert_eq!(rational_reconstruction(&Integer::from_i128(5), &Integer::from_i128(11)), Some(Rational::new(&Integer::from_i128(-1), &Integer::from_i128(2))));
+        //分子・分母が√(11/2)を超える分数しか該当しない場合は復元できない
+        assert_eq!(rational_reconstruction(&Integer::from_i128(4), &Integer::from_i128(11)), None);
+    }
+
+    #[test]
+    fn test_factorial(){
+        let expected = [1u128, 1, 2, 6, 24, 120, 720, 5040, 40320];
+        for (n, value) in expected.iter().enumerate() {
+            assert_eq!(factorial(n as u64), Integer::from_u128(*value));
+        }
+        //逐次的に掛けた結果と比較する
+        let mut naive = Integer::from_u128(1);
+        for k in 1..=300u128 {
+            naive = &naive * &Integer::from_u128(k);
+        }
+        assert_eq!(factorial(300), naive);
+        assert_eq!(double_factorial(0), Integer::from_u128(1));
+        assert_eq!(double_factorial(9), Integer::from_u128(945));
+        assert_eq!(double_factorial(10), Integer::from_u128(3840));
+    }
+
+    #[test]
+    fn test_binomial(){
+        assert_eq!(binomial(10, 3), Integer::from_u128(120));
+        assert_eq!(binomial(10, 0), Integer::from_u128(1));
+        assert_eq!(binomial(3, 5), Integer::from_u128(0));
+        assert_eq!(binomial(100, 50), "100891344545564193334812497256".parse().unwrap());
+        assert_eq!(multinomial(&[2, 3, 4]), Integer::from_u128(1260));
+        assert_eq!(multinomial(&[]), Integer::from_u128(1));
+        let catalans: Vec<Integer> = (0..8).map(catalan).collect();
+        assert_eq!(catalans, [1u128, 1, 2, 5, 14, 42, 132, 429].map(Integer::from_u128));
+    }
+
+    #[test]
+    fn test_fibonacci_lucas(){
+        let fibonaccis: Vec<Integer> = (0..10).map(fibonacci).collect();
+        assert_eq!(fibonaccis, [0u128, 1, 1, 2, 3, 5, 8, 13, 21, 34].map(Integer::from_u128));
+        let lucases: Vec<Integer> = (0..8).map(lucas).collect();
+        assert_eq!(lucases, [2u128, 1, 3, 4, 7, 11, 18, 29].map(Integer::from_u128));
+        assert_eq!(fibonacci(300), "222232244629420445529739893461909967206666939096499764990979600".parse().unwrap());
+    }
+
+    #[test]
+    fn test_stirling_bell(){
+        assert_eq!(stirling_first(5, 2), Integer::from_u128(50));
+        assert_eq!(stirling_first(4, 4), Integer::from_u128(1));
+        assert_eq!(stirling_first(4, 0), Integer::from_u128(0));
+        assert_eq!(stirling_first(0, 0), Integer::from_u128(1));
+        assert_eq!(stirling_second(5, 2), Integer::from_u128(15));
+        assert_eq!(stirling_second(10, 4), Integer::from_u128(34105));
+        assert_eq!(stirling_second(3, 4), Integer::from_u128(0));
+        let bells: Vec<Integer> = (0..8).map(bell).collect();
+        assert_eq!(bells, [1u128, 1, 2, 5, 15, 52, 203, 877].map(Integer::from_u128));
+        //B(n) = Σ_k {n, k}
+        let mut sum = Integer::from_u128(0);
+        for k in 0..=30 {
+            sum = &sum + &stirling_second(30, k);
+        }
+        assert_eq!(bell(30), sum);
+    }
+
+    #[test]
+    fn test_factorize(){
+        let factors = |n: &str| -> Vec<(String, u32)> {
+            factorize(&n.parse().unwrap()).into_iter().map(|(prime, exponent)| (prime.to_string(), exponent)).collect()
+        };
+        assert_eq!(factors("1"), vec![]);
+        assert_eq!(factors("-360"), vec![("2".to_string(), 3), ("3".to_string(), 2), ("5".to_string(), 1)]);
+        //(2^61 - 1)·(2^31 - 1)^2
+        assert_eq!(
+            factors("10633823956375806666641571278131036159"),
+            vec![("2147483647".to_string(), 2), ("2305843009213693951".to_string(), 1)]
+        );
+        assert_eq!(factors("1000000016000000063"), vec![("1000000007".to_string(), 1), ("1000000009".to_string(), 1)]);
+        assert!(is_probable_prime(&"170141183460469231731687303715884105727".parse().unwrap()));
+        //カーマイケル数
+        assert!(!is_probable_prime(&Integer::from_u128(561)));
+        assert!(!is_probable_prime(&Integer::from_u128(3215031751)));
+    }
+
+    #[test]
+    fn test_arithmetic_functions(){
+        assert_eq!(euler_phi(&Integer::from_u128(1)), Integer::from_u128(1));
+        assert_eq!(euler_phi(&Integer::from_u128(36)), Integer::from_u128(12));
+        assert_eq!(euler_phi(&Integer::from_u128(1_000_000_007)), Integer::from_u128(1_000_000_006));
+        assert_eq!(divisors(&Integer::from_i128(-12)), [1u128, 2, 3, 4, 6, 12].map(Integer::from_u128));
+        assert_eq!(sigma_k(&Integer::from_u128(12), 0), Integer::from_u128(6));
+        assert_eq!(sigma_k(&Integer::from_u128(12), 1), Integer::from_u128(28));
+        assert_eq!(sigma_k(&Integer::from_u128(12), 2), Integer::from_u128(210));
+        let mobius_values: Vec<i8> = (1..=10).map(|n| mobius(&Integer::from_u128(n))).collect();
+        assert_eq!(mobius_values, [1, -1, -1, 0, -1, 1, -1, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_kronecker_symbol(){
+        let expected = [(3, 8, -1), (5, 8, -1), (7, 8, 1), (2, 6, 0), (-1, -5, -1), (5, -1, 1), (-5, -1, -1), (1, 0, 1), (2, 0, 0), (11, 12, -1), (-3, 20, -1), (-3, 4, 1)];
+        for (a, n, symbol) in expected {
+            assert_eq!(kronecker_symbol(&Integer::from_i128(a), &Integer::from_i128(n)), symbol, "({}/{})", a, n);
+        }
+    }
+
+    #[test]
+    fn test_primitive_root_discrete_log(){
+        let root = |n: u128| primitive_root(&Integer::from_u128(n)).map(|root| root.to_string());
+        assert_eq!(root(2), Some("1".to_string()));
+        assert_eq!(root(7), Some("3".to_string()));
+        assert_eq!(root(1_000_000_007), Some("5".to_string()));
+        assert_eq!(root(50), Some("3".to_string()));
+        assert_eq!(root(8), None);
+        assert_eq!(root(15), None);
+        let log = |g: u128, h: u128, m: u128| discrete_log(&Integer::from_u128(g), &Integer::from_u128(h), &Integer::from_u128(m));
+        assert_eq!(log(3, 13, 17), Some(Integer::from_u128(4)));
+        assert_eq!(log(2, 1, 7), Some(Integer::from_u128(0)));
+        //2の位数は3なので3は2のべきにならない
+        assert_eq!(log(2, 3, 7), None);
+        assert_eq!(log(5, 880028913, 1_000_000_007), Some(Integer::from_u128(151840682)));
+        assert_eq!(log(7, 19, 36), Some(Integer::from_u128(3)));
+    }
+
+    #[test]
+    fn test_bernoulli_euler(){
+        let expected = ["1", "-1/2", "1/6", "0", "-1/30", "0", "1/42", "0", "-1/30", "0", "5/66", "0", "-691/2730", "0", "7/6"];
+        let expected: Vec<Rational> = expected.iter().map(|value| value.parse().unwrap()).collect();
+        assert_eq!(bernoulli_numbers(14), expected);
+        assert_eq!(bernoulli(0), Rational::one());
+        assert_eq!(bernoulli(1), "-1/2".parse::<Rational>().unwrap());
+        assert_eq!(bernoulli(60), "-1215233140483755572040304994079820246041491/56786730".parse::<Rational>().unwrap());
+        let expected = [1i128, 0, -1, 0, 5, 0, -61, 0, 1385, 0, -50521];
+        assert_eq!(euler_numbers(10), expected.map(Integer::from_i128));
+        assert_eq!(euler_number(7), Integer::from_i128(0));
+        assert_eq!(euler_number(30), "-441543893249023104553682821".parse::<Integer>().unwrap());
+    }
+
+    #[test]
+    fn test_harmonic_zeta(){
+        assert_eq!(harmonic(0), Rational::zero());
+        assert_eq!(harmonic(1), Rational::one());
+        assert_eq!(harmonic(10), "7381/2520".parse::<Rational>().unwrap());
+        //逐次的に足し合わせた結果と比較する
+        let mut sum = Rational::zero();
+        for k in 1..=200u64 {
+            sum += &Rational::new(&Integer::one(), &Integer::from_u64(k * k));
+        }
+        assert_eq!(zeta_partial_sum(2, 200), sum);
+        assert_eq!(zeta_partial_sum(4, 3), "1393/1296".parse::<Rational>().unwrap());
+        assert_eq!(zeta_even_coefficient(1), "1/6".parse::<Rational>().unwrap());
+        assert_eq!(zeta_even_coefficient(2), "1/90".parse::<Rational>().unwrap());
+        assert_eq!(zeta_even_coefficient(6), "691/638512875".parse::<Rational>().unwrap());
+    }
+
+    #[test]
+    fn test_binary_splitting_exp(){
+        //Σ_{n=0}^{9} 1/n!
+        let sum = binary_splitting_sum(
+            0,
+            10,
+            &|_| Integer::from_u128(1),
+            &|n| Integer::from_u128(n.max(1)),
+            &|_| Integer::from_u128(1),
+        );
+        assert_eq!(sum, "98641/36288".parse::<Rational>().unwrap());
+    }
+
+    #[test]
+    fn test_binary_splitting_triple(){
+        //Σ_{n=1}^{4} n (-1/2)^n = -1/2 + 2/4 - 3/8 + 4/16
+        let (p, q, t) = binary_splitting(
+            1,
+            5,
+            &|_| Integer::from_i128(-1),
+            &|_| Integer::from_u128(2),
+            &|n| Integer::from_u128(n),
+        );
+        assert_eq!(p, Integer::from_i128(1));
+        assert_eq!(q, Integer::from_u128(16));
+        assert_eq!(Rational::new(&t, &q), "-1/8".parse::<Rational>().unwrap());
+    }
+
+    #[test]
+    fn test_gcd_sign_and_zero(){
+        assert_eq!(gcd(&Integer::from_i128(-12),&Integer::from_i128(18)),Integer::from_i128(6));
+        assert_eq!(gcd(&Integer::from_i128(12),&Integer::from_i128(-18)),Integer::from_i128(6));
+        assert_eq!(gcd(&Integer::from_i128(0),&Integer::from_i128(-7)),Integer::from_i128(7));
+        assert_eq!(gcd(&Integer::from_i128(0),&Integer::from_i128(0)),Integer::from_i128(0));
     }
 
 
@@ -1,220 +1,594 @@
 use std::cmp;
 
-use crate::num::{Digit, Integer, Rational};
+use crate::digits::DigitVec;
+use crate::num::Digit;
+#[cfg(any(feature = "limb-u32", feature = "limb-u64"))]
+use crate::num::DoubleDigit;
+use crate::parallel;
 
+///上位桁の余った桁を除去する。
+///cuting uppernumber zeros.
+///
+pub(crate) fn cut_upper_zeros(number: &[Digit]) -> Vec<Digit> {
+    let length = significant_length(number);
+    return number[..length].to_vec();
+}
 
+///上位桁の余った桁をその場で除去する(最低1桁は残す)
+pub(crate) fn trim_upper_zeros(number: &mut DigitVec) {
+    let length = significant_length(number);
+    number.truncate(length);
+}
 
-/**巨大な整数の加算処理(筆算アルゴリズム)
-Internal decimal adder
-# Arguments
- * 'lhs' - 右辺値
- * 'rhs' - 左辺値
-配列を多倍長整数と見做して加算する。
-なお、この関数がサポートする数は自然数と0のみ。
-# Returns
-計算結果を表す任意精度整数値
-# Compute cost
-この計算量はO(N)である。
- */
-pub(crate) fn arbitrary_precision_add(lhs: &[Digit], rhs: &[Digit]) -> Vec<Digit> {
-    let argsize = cmp::max(lhs.len(), rhs.len());
-    let mut array: Vec<Digit> = Vec::with_capacity(argsize + 1);
-    let mut carry: Digit = 0;
-    for i_ in 0..argsize {
-        let lhs_digit = *lhs.get(i_).unwrap_or(&0);
-        let rhs_digit = *rhs.get(i_).unwrap_or(&0);
-        let result_add = lhs_digit.overflowing_add(rhs_digit);
-        let result_number = result_add.0.overflowing_add(carry);
-        carry = 0;
-        if true == result_add.1 {
-            carry += 1;
-        }
-        if true == result_number.1 {
-            carry += 1;
+///上位の0を除いた桁数(最低1)
+pub(crate) fn significant_length(number: &[Digit]) -> usize {
+    return number.iter().rposition(|digit| *digit != 0).map_or(1, |top| top + 1).min(number.len());
+}
+
+///1桁同士の乗算結果を(下位桁, 上位桁)で返す
+#[cfg(any(feature = "limb-u32", feature = "limb-u64"))]
+pub(crate) fn widening_mul(lhs: Digit, rhs: Digit) -> (Digit, Digit) {
+    let product = lhs as DoubleDigit * rhs as DoubleDigit;
+    return (product as Digit, (product >> Digit::BITS) as Digit);
+}
+
+///1桁同士の乗算結果を(下位桁, 上位桁)で返す
+///
+///2倍幅の型がないため、半分の幅に分割して筆算を行う。
+#[cfg(not(any(feature = "limb-u32", feature = "limb-u64")))]
+pub(crate) fn widening_mul(lhs: Digit, rhs: Digit) -> (Digit, Digit) {
+    const HALF: u32 = Digit::BITS / 2;
+    const LOW_MASK: Digit = Digit::MAX >> HALF;
+    let (lhs_low, lhs_high) = (lhs & LOW_MASK, lhs >> HALF);
+    let (rhs_low, rhs_high) = (rhs & LOW_MASK, rhs >> HALF);
+    let low = lhs_low * rhs_low;
+    let (middle, middle_carry) = (lhs_low * rhs_high).overflowing_add(lhs_high * rhs_low);
+    let (result_low, low_carry) = low.overflowing_add(middle << HALF);
+    let result_high = lhs_high * rhs_high
+        + (middle >> HALF)
+        + ((middle_carry as Digit) << HALF)
+        + low_carry as Digit;
+    return (result_low, result_high);
+}
+
+///128bit整数を桁の列に変換する
+pub(crate) fn digits_from_u128(value: u128) -> DigitVec {
+    let mut digits = DigitVec::new();
+    let mut rest = value;
+    loop {
+        digits.push(rest as Digit);
+        rest = rest.checked_shr(Digit::BITS).unwrap_or(0);
+        if rest == 0 {
+            return digits;
         }
-        array.push(result_number.0);
     }
-    if 0 < carry {
-        array.push(carry);
+}
+
+///128bitに収まる桁の列を128bit整数に変換する(あふれた上位桁は捨てる)
+#[allow(clippy::unnecessary_cast)]
+pub(crate) fn digits_to_u128(number: &[Digit]) -> u128 {
+    return number
+        .iter()
+        .rev()
+        .fold(0u128, |acc, digit| acc.checked_shl(Digit::BITS).unwrap_or(0) | *digit as u128);
+}
+
+/**絶対値への加算をその場で行う
+In-place adder.
+
+lhsの容量が足りる限り新たな領域を確保しない。
+*/
+pub(crate) fn add_assign(lhs: &mut DigitVec, rhs: &[Digit]) {
+    if lhs.len() < rhs.len() {
+        lhs.resize(rhs.len(), 0);
+    }
+    if add_to_slice(lhs, rhs) {
+        lhs.push(1);
     }
-    return array;
 }
 
-/**  減算計算を行う
-Internal substitutor imprements.
+/**絶対値からの減算をその場で行う
+In-place substitutor.
+# Returns
+結果が負(rhsの方が大きい)の場合true。lhsには差の絶対値が入る。
+*/
+pub(crate) fn sub_assign(lhs: &mut DigitVec, rhs: &[Digit]) -> bool {
+    let negative = compare_abs(lhs, rhs) == cmp::Ordering::Less;
+    if negative {
+        //lhsの有効桁はrhsより短いので、rhsの長さに揃えてから rhs - lhs で上書きする
+        lhs.resize(rhs.len(), 0);
+        rsub_from_slice(lhs, rhs);
+    } else {
+        sub_from_slice(lhs, rhs);
+    }
+    trim_upper_zeros(lhs);
+    return negative;
+}
 
-# 引数 Arguments
- * 'lhs' - 左辺値
- * 'rhs' - 右辺値
+///1桁の数を絶対値にその場で乗算する
+pub(crate) fn mul_small_assign(lhs: &mut DigitVec, rhs: Digit) {
+    let carry = mul_limb(lhs, rhs);
+    if carry != 0 {
+        lhs.push(carry);
+    }
+    trim_upper_zeros(lhs);
+}
 
-# 戻り値 returns
-戻り値は(Vec<Digit>,bool)のタプルで返される
-1. Vec<Digit> 計算後の値
-2. bool 符号(trueのとき負)
+/*
+以下の基本演算はいずれも、重なっている桁を境界検査なしの単純なループで処理し、
+残りの桁には桁上がり(桁借り)がある間だけ伝播させる。
 */
-pub(crate) fn arbitrary_precision_sub(lhs: &[Digit], rhs: &[Digit]) -> (Vec<Digit>, bool) {
-    let array_len: usize = cmp::max(lhs.len(), rhs.len());
-    let mut array_result: Vec<Digit> = vec![0 as Digit;array_len];
-    let mut carry_down: Digit = 0;
-    for _i in 0..array_len {
-        let rhs_digit = *rhs.get(_i).unwrap_or(&0);
-        let lhs_digit = *lhs.get(_i).unwrap_or(&0);
-        let result_substitute = lhs_digit.overflowing_sub(rhs_digit);
-        let result_number = result_substitute.0.overflowing_sub(carry_down);
-        carry_down = 0;
-        if result_substitute.1 {
-            carry_down += 1;
-        }
-        if result_number.1 {
-            carry_down += 1;
-        }
-        array_result[_i] = result_number.0;
+
+///accにxを加算し、accからあふれた桁上がりを返す(accはx以上の長さを持つこと)
+fn add_to_slice(acc: &mut [Digit], x: &[Digit]) -> bool {
+    let (overlap, tail) = acc.split_at_mut(x.len());
+    let mut carry = false;
+    for (digit, x_digit) in overlap.iter_mut().zip(x) {
+        let (value, carry_add) = digit.overflowing_add(*x_digit);
+        let (value, carry_carry) = value.overflowing_add(carry as Digit);
+        *digit = value;
+        carry = carry_add | carry_carry;
     }
+    return carry && propagate_carry(tail);
+}
 
-    return match carry_down {
-        0 => (cut_upper_zeros(&array_result), false),
-        1 => {
-            return (cut_upper_zeros(&to_min_complement(&array_result)), true);
-        }
-        _ => panic!("Something wrong in substitution algorithm. Please contact to developer.\nERROR_TYPE:Multiple carry down occured."), //余分に桁が降りてきているので計算ミス
-    };
+///accからxを減算し、accからの桁借りを返す(accはx以上の長さを持つこと)
+fn sub_from_slice(acc: &mut [Digit], x: &[Digit]) -> bool {
+    let (overlap, tail) = acc.split_at_mut(x.len());
+    let mut borrow = false;
+    for (digit, x_digit) in overlap.iter_mut().zip(x) {
+        let (value, borrow_sub) = digit.overflowing_sub(*x_digit);
+        let (value, borrow_carry) = value.overflowing_sub(borrow as Digit);
+        *digit = value;
+        borrow = borrow_sub | borrow_carry;
+    }
+    return borrow && propagate_borrow(tail);
 }
 
-/**最小の補数表現にする
- * また、補数から絶対値に戻すこともできる。
- * */
-pub(crate) fn to_min_complement(number: &[Digit]) -> Vec<Digit> {
-    let mut array_result = number.to_vec();
-    let mut need_carry: bool = true;
-    let mut first_zeros: bool = true;
-    for i_ in 0..array_result.len() {
-        if first_zeros && array_result[i_] == 0 {
-            //ここでは桁上がりできないので0のまま放置
-        } else {
-            first_zeros = false;
-            array_result[i_] = &Digit::MAX - &array_result[i_]; //補数表現から戻す
-            if need_carry {
-                array_result[i_] += 1; //最初の桁だけ1加算する。
-                need_carry = false;
-            }
-        }
+///accをx - accで上書きし、桁借りを返す(accとxは同じ長さを持つこと)
+fn rsub_from_slice(acc: &mut [Digit], x: &[Digit]) -> bool {
+    let mut borrow = false;
+    for (digit, x_digit) in acc.iter_mut().zip(x) {
+        let (value, borrow_sub) = x_digit.overflowing_sub(*digit);
+        let (value, borrow_carry) = value.overflowing_sub(borrow as Digit);
+        *digit = value;
+        borrow = borrow_sub | borrow_carry;
     }
-    return array_result;
+    return borrow;
 }
 
-///上位桁の余った桁を除去する。
-///cuting uppernumber zeros.
-///
-pub(crate) fn cut_upper_zeros(number: &[Digit]) -> Vec<Digit> {
-    let mut num_mut = number.to_vec();
-    for _i in (1..num_mut.len()).rev() {
-        if num_mut[_i] == 0 as Digit {
-            num_mut.remove(_i);
-        } else {
-            return num_mut;
+///1の桁上がりを伝播させ、最上位からあふれたかを返す
+fn propagate_carry(acc: &mut [Digit]) -> bool {
+    for digit in acc.iter_mut() {
+        let (value, carry) = digit.overflowing_add(1);
+        *digit = value;
+        if !carry {
+            return false;
         }
     }
-    return num_mut;
-}
-
-///128bit符号無し整数同士の安全な乗算
-pub(crate) fn safe_multiply_digit(lhs: Digit, rhs: Digit) -> Vec<Digit> {
-    const HIGH_MASK: Digit = Digit::MAX << (Digit::BITS / 2);
-    const LOW_MASK: Digit = Digit::MAX ^ HIGH_MASK;
-    let lhs_sep: (Digit, Digit) = (lhs & LOW_MASK, (lhs & HIGH_MASK) >> (Digit::BITS / 2));
-    let rhs_sep: (Digit, Digit) = (rhs & LOW_MASK, (rhs & HIGH_MASK) >> (Digit::BITS / 2));
-    let res_middle = (lhs_sep.0 * rhs_sep.1).overflowing_add(lhs_sep.1 * rhs_sep.0);
-    let res_low =
-        (lhs_sep.0 * rhs_sep.0).overflowing_add((res_middle.0 & LOW_MASK) << (Digit::BITS / 2));
-    let res_high: Digit = lhs_sep.1 * rhs_sep.1
-        + match res_middle.1 {
-            true => (1 as Digit) << (Digit::BITS / 2),
-            false => 0 as Digit,
-        }
-        + match res_low.1 {
-            true => 1 as Digit,
-            false => 0 as Digit,
+    return true;
+}
+
+///1の桁借りを伝播させ、最上位を越えて借りたかを返す
+fn propagate_borrow(acc: &mut [Digit]) -> bool {
+    for digit in acc.iter_mut() {
+        let (value, borrow) = digit.overflowing_sub(1);
+        *digit = value;
+        if !borrow {
+            return false;
         }
-        + ((res_middle.0 & HIGH_MASK) >> (Digit::BITS / 2));
-    return cut_upper_zeros(&vec![res_low.0, res_high]);
+    }
+    return true;
+}
+
+///accに1桁の数をその場で乗算し、上位にあふれた1桁を返す
+fn mul_limb(acc: &mut [Digit], multiplier: Digit) -> Digit {
+    let mut carry: Digit = 0;
+    for digit in acc.iter_mut() {
+        let (low, high) = widening_mul(*digit, multiplier);
+        let (low, overflow) = low.overflowing_add(carry);
+        *digit = low;
+        carry = high + overflow as Digit;
+    }
+    return carry;
 }
 
-pub(crate) fn append_upper_zeros(number: &[Digit], needed_size: usize) -> Vec<Digit> {
-    let num_append_digits = needed_size - number.len();
-    return [number, &vec![0 as Digit; num_append_digits]].concat();
+///acc[..x.len()]にx * multiplierを加算し、上位にあふれた1桁を返す(accはx以上の長さを持つこと)
+fn addmul_limb(acc: &mut [Digit], x: &[Digit], multiplier: Digit) -> Digit {
+    let mut carry: Digit = 0;
+    for (digit, x_digit) in acc[..x.len()].iter_mut().zip(x) {
+        let (low, high) = widening_mul(*x_digit, multiplier);
+        let (low, overflow_acc) = low.overflowing_add(*digit);
+        let (low, overflow_carry) = low.overflowing_add(carry);
+        *digit = low;
+        //high <= Digit::MAX - 1 なので2回の桁上がりを足してもあふれない
+        carry = high + overflow_acc as Digit + overflow_carry as Digit;
+    }
+    return carry;
 }
 
+/// この桁数以下では筆算で乗算する
+const KARATSUBA_THRESHOLD: usize = 24;
+
 ///正の整数同士を乗算する関数
 ///
+///Karatsuba法の作業領域は最初に一度だけ確保し、再帰呼び出しの間で使い回す。
 pub(crate) fn arbitrary_precision_mul(lhs: &[Digit], rhs: &[Digit]) -> Vec<Digit> {
-    assert!(lhs.len() != 0 && rhs.len() != 0);
-
-    if &vec![0 as Digit; lhs.len()] == lhs || &vec![0 as Digit; rhs.len()] == rhs {
+    assert!(!lhs.is_empty() && !rhs.is_empty());
+    let lhs = &lhs[..significant_length(lhs)];
+    let rhs = &rhs[..significant_length(rhs)];
+    if lhs == [0 as Digit] || rhs == [0 as Digit] {
         return vec![0 as Digit];
     }
 
-    if &vec![1 as Digit] == &cut_upper_zeros(lhs) {
-        return rhs.to_vec();
+    let mut result = vec![0 as Digit; lhs.len() + rhs.len()];
+    let mut scratch = vec![0 as Digit; mul_scratch_len(lhs.len(), rhs.len())];
+    mul_to(&mut result, lhs, rhs, &mut scratch);
+    result.truncate(significant_length(&result));
+    return result;
+}
+
+///`mul_to`に必要な作業領域の大きさ(`mul_to`と同じ分岐をたどって求める)
+fn mul_scratch_len(lhs_len: usize, rhs_len: usize) -> usize {
+    let (long, short) = (cmp::max(lhs_len, rhs_len), cmp::min(lhs_len, rhs_len));
+    if short <= KARATSUBA_THRESHOLD {
+        return 0;
     }
+    if long == short {
+        let low = short / 2;
+        let high = short - low;
+        return 6 * high + 1 + cmp::max(mul_scratch_len(low, low), mul_scratch_len(high, high));
+    }
+    let remain = long % short;
+    let chunk = match remain {
+        0 => 0,
+        _ => mul_scratch_len(short, remain),
+    };
+    return 2 * short + cmp::max(mul_scratch_len(short, short), chunk);
+}
 
-    if &vec![1 as Digit] == &cut_upper_zeros(rhs) {
-        return lhs.to_vec();
+///out[..lhs.len() + rhs.len()]に積を書き込む
+fn mul_to(out: &mut [Digit], lhs: &[Digit], rhs: &[Digit], scratch: &mut [Digit]) {
+    let (long, short) = match lhs.len() < rhs.len() {
+        true => (rhs, lhs),
+        false => (lhs, rhs),
+    };
+    let out = &mut out[..long.len() + short.len()];
+    if short.len() <= KARATSUBA_THRESHOLD {
+        schoolbook_mul(out, long, short);
+        return;
     }
+    if long.len() == short.len() {
+        karatsuba_mul(out, long, short, scratch);
+        return;
+    }
+    //長い方を短い方の桁数ごとに区切って掛け、ずらしながら足し合わせる
+    out.fill(0);
+    let (partial, scratch) = scratch.split_at_mut(2 * short.len());
+    for (index, chunk) in long.chunks(short.len()).enumerate() {
+        let partial = &mut partial[..chunk.len() + short.len()];
+        mul_to(partial, chunk, short, scratch);
+        add_to_slice(&mut out[index * short.len()..], partial);
+    }
+}
 
-    if lhs.len() < rhs.len() {
-        return arbitrary_precision_mul(&append_upper_zeros(lhs, rhs.len()), rhs);
+///筆算による乗算
+fn schoolbook_mul(out: &mut [Digit], lhs: &[Digit], rhs: &[Digit]) {
+    out.fill(0);
+    for (i_, rhs_digit) in rhs.iter().enumerate() {
+        if *rhs_digit == 0 {
+            continue;
+        }
+        out[i_ + lhs.len()] = addmul_limb(&mut out[i_..], lhs, *rhs_digit);
     }
-    if lhs.len() > rhs.len() {
-        return arbitrary_precision_mul(lhs, &append_upper_zeros(rhs, lhs.len()));
+}
+
+///作業領域を新たに確保してout[..lhs.len() + rhs.len()]に積を書き込む
+fn mul_to_owned_scratch(out: &mut [Digit], lhs: &[Digit], rhs: &[Digit]) {
+    let mut scratch = vec![0 as Digit; mul_scratch_len(lhs.len(), rhs.len())];
+    mul_to(out, lhs, rhs, &mut scratch);
+}
+
+///同じ桁数同士のKaratsuba法による乗算
+fn karatsuba_mul(out: &mut [Digit], lhs: &[Digit], rhs: &[Digit], scratch: &mut [Digit]) {
+    let n = lhs.len();
+    let low = n / 2;
+    let high = n - low;
+    let (lhs_low, lhs_high) = lhs.split_at(low);
+    let (rhs_low, rhs_high) = rhs.split_at(low);
+
+    let (diff_lhs, scratch) = scratch.split_at_mut(high);
+    let (diff_rhs, scratch) = scratch.split_at_mut(high);
+    let (middle, scratch) = scratch.split_at_mut(2 * high);
+    let (sum, scratch) = scratch.split_at_mut(2 * high + 1);
+
+    //(lhs_high - lhs_low)(rhs_low - rhs_high) = 交差項 - z0 - z2
+    let lhs_negative = abs_diff_to(diff_lhs, lhs_high, lhs_low);
+    let rhs_negative = abs_diff_to(diff_rhs, rhs_low, rhs_high);
+
+    //z0 = 下位同士の積, z2 = 上位同士の積をそれぞれoutの下位・上位に置く
+    let (out_low, out_high) = out.split_at_mut(2 * low);
+    if parallel::ENABLED && n >= parallel::mul_threshold() {
+        //3つの部分積は独立しているので、それぞれ専用の作業領域で並列に計算する
+        parallel::join(
+            || mul_to_owned_scratch(out_low, lhs_low, rhs_low),
+            || {
+                parallel::join(
+                    || mul_to_owned_scratch(out_high, lhs_high, rhs_high),
+                    || mul_to_owned_scratch(middle, diff_lhs, diff_rhs),
+                )
+            },
+        );
+    } else {
+        mul_to(out_low, lhs_low, rhs_low, scratch);
+        mul_to(out_high, lhs_high, rhs_high, scratch);
+        mul_to(middle, diff_lhs, diff_rhs, scratch);
     }
 
-    let length = lhs.len();
+    sum[..2 * high].copy_from_slice(&out[2 * low..]);
+    sum[2 * high] = 0;
+    add_to_slice(sum, &out[..2 * low]);
+    match lhs_negative ^ rhs_negative {
+        false => add_to_slice(sum, middle),
+        true => sub_from_slice(sum, middle),
+    };
+    add_to_slice(&mut out[low..], sum);
+}
 
-    match length {
-        1 => {
-            let result = safe_multiply_digit(lhs[0], rhs[0]);
-            return result;
+///outに|lhs - rhs|を書き込み、負であったかを返す(lhsはrhs以上の長さを持つこと)
+fn abs_diff_to(out: &mut [Digit], lhs: &[Digit], rhs: &[Digit]) -> bool {
+    let negative = compare_abs(lhs, rhs) == cmp::Ordering::Less;
+    out.fill(0);
+    match negative {
+        false => {
+            out[..lhs.len()].copy_from_slice(lhs);
+            sub_from_slice(out, rhs);
         }
-        _ => {
-            let split_point = length / 2;
-            let lhs_split = (&lhs[0..split_point], &lhs[split_point..length]);
-            let rhs_split = (&rhs[0..split_point], &rhs[split_point..length]);
-
-            let mul_down = match lhs_split.0.len() {
-                0 => panic!("Error in split logic. (generate zero sized array.)"),
-                1 => safe_multiply_digit(lhs_split.0[0], rhs_split.0[0]),
-                _ => arbitrary_precision_mul(&lhs_split.0, &rhs_split.0),
-            };
+        true => {
+            out[..rhs.len()].copy_from_slice(rhs);
+            sub_from_slice(out, lhs);
+        }
+    }
+    return negative;
+}
 
-            let mul_up = match lhs_split.1.len() {
-                0 => panic!("Error in split logic. (generator zero sized array.)"),
-                1 => safe_multiply_digit(lhs_split.1[0], rhs_split.1[0]),
-                _ => arbitrary_precision_mul(&lhs_split.1, &rhs_split.1),
-            };
+/**絶対値同士の大小比較を行う
+Compare two magnitudes.
 
-            let lhs_sub_result = arbitrary_precision_sub(lhs_split.1, lhs_split.0);
-            let rhs_sub_result = arbitrary_precision_sub(rhs_split.0, rhs_split.1);
-            let mul_karatsuba = arbitrary_precision_mul(&lhs_sub_result.0, &rhs_sub_result.0);
-            let add_karatsuba = arbitrary_precision_add(&mul_down, &mul_up);
-            let karatsuba = match lhs_sub_result.1 ^ rhs_sub_result.1 {
-                true => arbitrary_precision_sub(&add_karatsuba, &mul_karatsuba),
-                false => (
-                    arbitrary_precision_add(&add_karatsuba, &mul_karatsuba),
-                    lhs_sub_result.1,
-                ),
-            };
-            let lower = mul_down;
-            let middle = [vec![0 as Digit; split_point], karatsuba.0].concat();
-            let upper = [vec![0 as Digit; split_point * 2], mul_up].concat();
-
-            let low_add_up = arbitrary_precision_add(&lower, &upper);
-            let result = match karatsuba.1 {
-                false => cut_upper_zeros(&arbitrary_precision_add(&low_add_up, &middle)),
-                true => cut_upper_zeros(&arbitrary_precision_sub(&low_add_up, &middle).0),
+上位桁の余分な0は無視して比較する。
+*/
+pub(crate) fn compare_abs(lhs: &[Digit], rhs: &[Digit]) -> cmp::Ordering {
+    let lhs = &lhs[..significant_length(lhs)];
+    let rhs = &rhs[..significant_length(rhs)];
+    if lhs.len() != rhs.len() {
+        return lhs.len().cmp(&rhs.len());
+    }
+    for (lhs_digit, rhs_digit) in lhs.iter().rev().zip(rhs.iter().rev()) {
+        if lhs_digit != rhs_digit {
+            return lhs_digit.cmp(rhs_digit);
+        }
+    }
+    return cmp::Ordering::Equal;
+}
+
+/// 除算用に桁を64bitごとに詰め直す(上位の0は除く)
+///
+/// 桁の幅によっては同じ型へのキャストとなるため、その警告は抑制する。
+#[allow(clippy::unnecessary_cast)]
+fn to_words(number: &[Digit]) -> Vec<u64> {
+    let mut result: Vec<u64> = Vec::with_capacity(number.len() * Digit::BITS as usize / 64 + 1);
+    let (mut word, mut filled) = (0u64, 0u32);
+    for digit in number {
+        let (mut digit, mut remain) = (*digit, Digit::BITS);
+        while remain > 0 {
+            let take = cmp::min(remain, 64 - filled);
+            word |= (digit as u64 & (u64::MAX >> (64 - take))) << filled;
+            digit = digit.checked_shr(take).unwrap_or(0);
+            remain -= take;
+            filled += take;
+            if filled == 64 {
+                result.push(word);
+                (word, filled) = (0, 0);
+            }
+        }
+    }
+    if filled > 0 {
+        result.push(word);
+    }
+    while result.last() == Some(&0) {
+        result.pop();
+    }
+    return result;
+}
+
+/// 64bitごとに詰めた値を元の桁に戻す
+#[allow(clippy::unnecessary_cast)]
+fn from_words(words: &[u64]) -> Vec<Digit> {
+    let mut result: Vec<Digit> = Vec::with_capacity(words.len() * 64 / Digit::BITS as usize + 1);
+    let (mut digit, mut filled) = (0 as Digit, 0u32);
+    for word in words {
+        let (mut word, mut remain) = (*word, 64u32);
+        while remain > 0 {
+            let take = cmp::min(remain, Digit::BITS - filled);
+            digit |= (word as Digit & (Digit::MAX >> (Digit::BITS - take))) << filled;
+            word = word.checked_shr(take).unwrap_or(0);
+            remain -= take;
+            filled += take;
+            if filled == Digit::BITS {
+                result.push(digit);
+                (digit, filled) = (0, 0);
+            }
+        }
+    }
+    if filled > 0 || result.is_empty() {
+        result.push(digit);
+    }
+    return cut_upper_zeros(&result);
+}
+
+/**64bit以下の除数による除算
+Short division by a single 64-bit word.
+# Returns
+(商, 余り)のタプル
+*/
+pub(crate) fn arbitrary_precision_div_small(lhs: &[Digit], rhs: u64) -> (Vec<Digit>, u64) {
+    assert!(rhs != 0, "Divide by zero");
+    let words = to_words(lhs);
+    let mut quotient = vec![0u64; words.len()];
+    let mut remain: u128 = 0;
+    for i_ in (0..words.len()).rev() {
+        let current = (remain << 64) | words[i_] as u128;
+        quotient[i_] = (current / rhs as u128) as u64;
+        remain = current % rhs as u128;
+    }
+    return (from_words(&quotient), remain as u64);
+}
+
+/**巨大な整数の除算処理(Knuthのアルゴリズム D)
+Internal long divider.
+# Arguments
+ * 'lhs' - 被除数
+ * 'rhs' - 除数
+
+どちらも絶対値として扱い、桁の幅によらず64bitごとに詰め直して筆算を行う。
+# Returns
+(商, 余り)のタプル
+# Panics
+除数が0の場合
+# Compute cost
+この計算量はO(N*M)である。
+*/
+pub(crate) fn arbitrary_precision_div_rem(lhs: &[Digit], rhs: &[Digit]) -> (Vec<Digit>, Vec<Digit>) {
+    let dividend = to_words(lhs);
+    let divisor = to_words(rhs);
+    if divisor.is_empty() {
+        panic!("Divide by zero");
+    }
+    if dividend.len() < divisor.len() {
+        return (vec![0 as Digit], from_words(&dividend));
+    }
+    if divisor.len() == 1 {
+        let (quotient, remain) = arbitrary_precision_div_small(lhs, divisor[0]);
+        return (quotient, from_words(&[remain]));
+    }
+
+    const BASE: u128 = 1 << 64;
+    let n = divisor.len();
+    let m = dividend.len();
+    //最上位桁の最上位bitが立つように正規化する
+    let shift = divisor[n - 1].leading_zeros();
+    let normalize = |src: &[u64], extend: bool| -> Vec<u64> {
+        let mut result: Vec<u64> = Vec::with_capacity(src.len() + 1);
+        let mut carry: u64 = 0;
+        for digit in src {
+            result.push((digit << shift) | carry);
+            carry = match shift {
+                0 => 0,
+                _ => digit >> (64 - shift),
             };
-            return result;
+        }
+        if extend {
+            result.push(carry);
+        }
+        result
+    };
+    let v = normalize(&divisor, false);
+    let mut u = normalize(&dividend, true);
+    let mut quotient = vec![0u64; m - n + 1];
+
+    for j in (0..=(m - n)).rev() {
+        let numerator = ((u[j + n] as u128) << 64) | u[j + n - 1] as u128;
+        let mut qhat = numerator / v[n - 1] as u128;
+        let mut rhat = numerator % v[n - 1] as u128;
+        while qhat >= BASE || qhat * v[n - 2] as u128 > ((rhat << 64) | u[j + n - 2] as u128) {
+            qhat -= 1;
+            rhat += v[n - 1] as u128;
+            if rhat >= BASE {
+                break;
+            }
+        }
+
+        //qhat * v を引く
+        let mut borrow: i128 = 0;
+        for i_ in 0..n {
+            let product = qhat * v[i_] as u128;
+            let t = u[i_ + j] as i128 - borrow - (product & (BASE - 1)) as i128;
+            u[i_ + j] = t as u64;
+            borrow = (product >> 64) as i128 - (t >> 64);
+        }
+        let t = u[j + n] as i128 - borrow;
+        u[j + n] = t as u64;
+
+        quotient[j] = qhat as u64;
+        if t < 0 {
+            //引きすぎたので1回分戻す
+            quotient[j] = quotient[j].wrapping_sub(1);
+            let mut carry: u128 = 0;
+            for i_ in 0..n {
+                let t = u[i_ + j] as u128 + v[i_] as u128 + carry;
+                u[i_ + j] = t as u64;
+                carry = t >> 64;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u64);
         }
     }
+
+    //余りの正規化を元に戻す
+    let mut remain = vec![0u64; n];
+    for i_ in 0..n {
+        remain[i_] = match shift {
+            0 => u[i_],
+            _ => (u[i_] >> shift) | (u[i_ + 1] << (64 - shift)),
+        };
+    }
+    return (from_words(&quotient), from_words(&remain));
+}
+
+/**左シフト(2のべき乗倍)
+Shift magnitude to the left by `bits`.
+*/
+pub(crate) fn shift_left_bits(number: &[Digit], bits: usize) -> Vec<Digit> {
+    let digit_shift = bits / Digit::BITS as usize;
+    let bit_shift = (bits % Digit::BITS as usize) as u32;
+    let mut result = vec![0 as Digit; digit_shift];
+    let mut carry: Digit = 0;
+    for digit in number {
+        result.push((digit << bit_shift) | carry);
+        carry = match bit_shift {
+            0 => 0,
+            _ => digit >> (Digit::BITS - bit_shift),
+        };
+    }
+    result.push(carry);
+    return cut_upper_zeros(&result);
+}
+
+/**右シフト(2のべき乗で割って切り捨て)
+Shift magnitude to the right by `bits`.
+*/
+pub(crate) fn shift_right_bits(number: &[Digit], bits: usize) -> Vec<Digit> {
+    let digit_shift = bits / Digit::BITS as usize;
+    let bit_shift = (bits % Digit::BITS as usize) as u32;
+    if digit_shift >= number.len() {
+        return vec![0 as Digit];
+    }
+    let upper = &number[digit_shift..];
+    let mut result: Vec<Digit> = Vec::with_capacity(upper.len());
+    for i_ in 0..upper.len() {
+        let next = *upper.get(i_ + 1).unwrap_or(&0);
+        result.push(match bit_shift {
+            0 => upper[i_],
+            _ => (upper[i_] >> bit_shift) | (next << (Digit::BITS - bit_shift)),
+        });
+    }
+    return cut_upper_zeros(&result);
+}
+
+/// 絶対値のbit長を求める
+pub(crate) fn bit_length(number: &[Digit]) -> u64 {
+    let number = cut_upper_zeros(number);
+    let top = number[number.len() - 1];
+    return (number.len() as u64 - 1) * Digit::BITS as u64 + (Digit::BITS - top.leading_zeros()) as u64;
 }
 
 /*
@@ -223,17 +597,45 @@ fn arbitrary_precision_int_to_string(value:&[Digit])->String{
 }*/
 
 #[cfg(test)]
-mod appdend_zeros_test {
-    use crate::{arithmetic_util::append_upper_zeros, num::Digit};
+mod upper_zeros_test {
+    use crate::{arithmetic_util::{cut_upper_zeros, trim_upper_zeros}, digits::DigitVec, num::Digit};
 
     #[test]
-    fn test_append() {
-        assert_eq!(
-            append_upper_zeros(&vec![1 as Digit], 3),
-            vec![1 as Digit, 0 as Digit, 0 as Digit]
-        );
+    fn test_cut() {
+        assert_eq!(cut_upper_zeros(&vec![1 as Digit, 0, 0]), vec![1 as Digit]);
+        assert_eq!(cut_upper_zeros(&vec![0 as Digit, 0]), vec![0 as Digit]);
+        let mut number = DigitVec::from(vec![0 as Digit, 2, 0, 0]);
+        trim_upper_zeros(&mut number);
+        assert_eq!(number[..], [0 as Digit, 2]);
+    }
+}
+
+#[cfg(test)]
+mod word_conversion_test {
+    use crate::{
+        arithmetic_util::{digits_from_u128, digits_to_u128, from_words, to_words},
+        num::Digit,
+    };
+
+    #[test]
+    fn test_u128_round_trip() {
+        for value in [0u128, 1, u64::MAX as u128 + 1, u128::MAX] {
+            let digits = digits_from_u128(value);
+            assert_eq!(digits.len(), (128 - value.leading_zeros()).div_ceil(Digit::BITS).max(1) as usize);
+            assert_eq!(digits_to_u128(&digits), value);
+        }
+    }
+
+    #[test]
+    fn test_words_round_trip() {
+        let digits = vec![Digit::MAX, 0, 5, Digit::MAX - 2, 0];
+        let words = to_words(&digits);
+        assert_eq!(words.len(), (4 * Digit::BITS as usize).div_ceil(64));
+        assert_eq!(from_words(&words), digits[..4].to_vec());
+        assert_eq!(from_words(&[]), vec![0 as Digit]);
     }
 }
+
 #[cfg(test)]
 mod mul_arbitrary_test {
     use crate::{arithmetic_util::arbitrary_precision_mul, num::Digit};
@@ -275,129 +677,191 @@ mod mul_arbitrary_test {
             vec![0 as Digit, 1234567 as Digit]
         );
     }
+
+    #[test]
+    fn test_karatsuba_both_differences_negative() {
+        //桁の基数をBとして (B + 5)(5B + 1) = 5B^2 + 26B + 5
+        assert_eq!(
+            arbitrary_precision_mul(&vec![5 as Digit, 1 as Digit], &vec![1 as Digit, 5 as Digit]),
+            vec![5 as Digit, 26 as Digit, 5 as Digit]
+        );
+    }
 }
 
 #[cfg(test)]
 mod mul_digit_test {
-    use crate::{arithmetic_util::safe_multiply_digit, num::Digit};
+    use crate::{arithmetic_util::widening_mul, num::Digit};
 
     #[test]
     fn test_multiply() {
-        assert_eq!(
-            safe_multiply_digit(Digit::MAX, 2 as Digit),
-            vec![Digit::MAX - 1 as Digit, 1]
-        );
+        assert_eq!(widening_mul(Digit::MAX, 2 as Digit), (Digit::MAX - 1 as Digit, 1));
     }
 
     #[test]
     fn mul_zero() {
-        assert_eq!(
-            safe_multiply_digit(Digit::MAX, 0 as Digit),
-            vec![0 as Digit]
-        );
+        assert_eq!(widening_mul(Digit::MAX, 0 as Digit), (0 as Digit, 0 as Digit));
     }
 
     #[test]
     fn test_maximum_mul(){
-        let actual = vec![1 as Digit,Digit::MAX-1];
-        assert_eq!(actual,safe_multiply_digit(Digit::MAX, Digit::MAX));
+        assert_eq!((1 as Digit, Digit::MAX - 1), widening_mul(Digit::MAX, Digit::MAX));
     }
 }
 
 #[cfg(test)]
-mod dedimal_add_kernel_tests {
-    use crate::{arithmetic_util::arbitrary_precision_add, num::Digit};
+mod in_place_kernel_test {
+    use crate::{
+        arithmetic_util::{
+            add_assign, addmul_limb, arbitrary_precision_mul, mul_limb, mul_small_assign, schoolbook_mul, sub_assign,
+        },
+        digits::DigitVec,
+        num::Digit,
+        test_util::XorShift,
+    };
+
+    #[test]
+    fn test_add_assign() {
+        let mut lhs = DigitVec::from(vec![Digit::MAX, Digit::MAX]);
+        add_assign(&mut lhs, &[1]);
+        assert_eq!(lhs[..], [0, 0, 1]);
+        let mut lhs = DigitVec::from(vec![5 as Digit]);
+        add_assign(&mut lhs, &[1, 2]);
+        assert_eq!(lhs[..], [6, 2]);
+    }
+
+    #[test]
+    fn test_sub_assign() {
+        let mut lhs = DigitVec::from(vec![0 as Digit, 1]);
+        assert!(!sub_assign(&mut lhs, &[1]));
+        assert_eq!(lhs[..], [Digit::MAX]);
+        let mut lhs = DigitVec::from(vec![3 as Digit]);
+        assert!(sub_assign(&mut lhs, &[1, 1]));
+        assert_eq!(lhs[..], [Digit::MAX - 1]);
+        let mut lhs = DigitVec::from(vec![7 as Digit, 7]);
+        assert!(!sub_assign(&mut lhs, &[7, 7]));
+        assert_eq!(lhs[..], [0]);
+    }
+
+    #[test]
+    fn test_mul_small_assign() {
+        let mut lhs = DigitVec::from(vec![Digit::MAX, 1]);
+        mul_small_assign(&mut lhs, 2);
+        assert_eq!(lhs[..], [Digit::MAX - 1, 3]);
+        mul_small_assign(&mut lhs, 0);
+        assert_eq!(lhs[..], [0]);
+    }
 
     #[test]
     fn test_1digit_add() {
-        assert_eq!(
-            vec![2 as Digit],
-            arbitrary_precision_add(&vec![1 as Digit], &vec![1 as Digit])
-        );
+        let mut lhs = DigitVec::from(vec![1 as Digit]);
+        add_assign(&mut lhs, &[1]);
+        assert_eq!(lhs[..], [2]);
     }
 
     #[test]
     fn test_carry() {
         //桁上がり確認
-        assert_eq!(
-            vec![0 as Digit, 1 as Digit],
-            arbitrary_precision_add(&vec![1 as Digit], &vec![Digit::MAX])
-        );
-        assert_eq!(
-            vec![0 as Digit, 1 as Digit],
-            arbitrary_precision_add(&vec![Digit::MAX], &vec![1 as Digit])
-        );
+        let mut lhs = DigitVec::from(vec![1 as Digit]);
+        add_assign(&mut lhs, &[Digit::MAX]);
+        assert_eq!(lhs[..], [0, 1]);
+        let mut lhs = DigitVec::from(vec![Digit::MAX]);
+        add_assign(&mut lhs, &[1]);
+        assert_eq!(lhs[..], [0, 1]);
     }
 
     #[test]
     fn test_carry_multiple() {
-        assert_eq!(
-            vec![
-                0 as Digit, 0 as Digit, 0 as Digit, 0 as Digit, 0 as Digit, 0 as Digit, 0 as Digit,
-                0 as Digit, 2 as Digit
-            ],
-            arbitrary_precision_add(
-                &vec![
-                    Digit::MAX,
-                    Digit::MAX,
-                    Digit::MAX,
-                    Digit::MAX,
-                    Digit::MAX,
-                    Digit::MAX,
-                    Digit::MAX,
-                    Digit::MAX,
-                    1 as Digit
-                ],
-                &vec![1 as Digit]
-            )
-        );
+        let mut lhs = DigitVec::from([vec![Digit::MAX; 8], vec![1]].concat());
+        add_assign(&mut lhs, &[1]);
+        assert_eq!(lhs[..], [vec![0; 8], vec![2]].concat());
     }
-}
-
-#[cfg(test)]
-mod decimal_sub_kernel_test {
-    use crate::{arithmetic_util::arbitrary_precision_sub, num::Digit};
 
     #[test]
     fn test_normal_substitute() {
-        assert_eq!(
-            (vec![90 as Digit], false),
-            arbitrary_precision_sub(&vec![100 as Digit], &vec![10 as Digit])
-        );
+        let mut lhs = DigitVec::from(vec![100 as Digit]);
+        assert!(!sub_assign(&mut lhs, &[10]));
+        assert_eq!(lhs[..], [90]);
     }
 
     #[test]
     fn test_carrige_down() {
-        assert_eq!(
-            (vec![Digit::MAX], false),
-            arbitrary_precision_sub(&vec![0 as Digit, 1 as Digit], &vec![1 as Digit])
-        );
+        let mut lhs = DigitVec::from(vec![0 as Digit, 1]);
+        assert!(!sub_assign(&mut lhs, &[1]));
+        assert_eq!(lhs[..], [Digit::MAX]);
     }
 
     #[test]
     fn test_result_negative() {
+        let mut lhs = DigitVec::from(vec![0 as Digit]);
+        assert!(sub_assign(&mut lhs, &[1]));
+        assert_eq!(lhs[..], [1]);
+    }
+
+    #[test]
+    fn test_result_negative_carrige_down() {
+        let mut lhs = DigitVec::from(vec![Digit::MAX]);
+        assert!(sub_assign(&mut lhs, &[0, 1]));
+        assert_eq!(lhs[..], [1]);
+    }
+
+    #[test]
+    fn test_result_negative_carrige_down_multiple() {
+        let mut lhs = DigitVec::from(vec![Digit::MAX, Digit::MAX]);
+        assert!(sub_assign(&mut lhs, &[0, 0, 1]));
+        assert_eq!(lhs[..], [1]);
+    }
+
+    #[test]
+    fn test_addmul_limb() {
+        //[MAX, MAX] + [MAX, MAX] * MAX = [0, MAX, MAX] (あふれる1桁はMAX)
+        let mut acc = vec![Digit::MAX, Digit::MAX, 7];
+        assert_eq!(addmul_limb(&mut acc, &[Digit::MAX, Digit::MAX], Digit::MAX), Digit::MAX);
+        assert_eq!(acc, vec![0, Digit::MAX, 7]);
+        let mut acc = vec![1 as Digit, 2, 3];
+        assert_eq!(mul_limb(&mut acc, 2), 0);
+        assert_eq!(acc, vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn test_karatsuba_matches_schoolbook() {
+        //擬似乱数で大きな桁数の値を作り、筆算の結果と比較する
+        let mut random = XorShift::new();
+        for (lhs_len, rhs_len) in [(60, 60), (100, 37), (75, 200), (130, 129)] {
+            let lhs = random.digits(lhs_len);
+            let rhs = random.digits(rhs_len);
+            let mut expected = vec![0 as Digit; lhs_len + rhs_len];
+            schoolbook_mul(&mut expected, &lhs, &rhs);
+            assert_eq!(arbitrary_precision_mul(&lhs, &rhs), expected);
+        }
+    }
+}
+
+#[cfg(test)]
+mod div_arbitrary_test {
+    use crate::{arithmetic_util::arbitrary_precision_div_rem, num::Digit};
+
+    #[test]
+    fn test_div_smaller() {
         assert_eq!(
-            (vec![1 as Digit], true),
-            arbitrary_precision_sub(&vec![0 as Digit], &vec![1 as Digit])
+            (vec![0 as Digit], vec![5 as Digit]),
+            arbitrary_precision_div_rem(&vec![5 as Digit], &vec![0 as Digit, 1 as Digit])
         );
     }
 
     #[test]
-    fn test_result_negative_carrige_down() {
+    fn test_div_single_digit() {
         assert_eq!(
-            (vec![1 as Digit], true),
-            arbitrary_precision_sub(&vec![Digit::MAX], &vec![0 as Digit, 1 as Digit])
+            (vec![0 as Digit, 1 as Digit], vec![1 as Digit]),
+            arbitrary_precision_div_rem(&vec![1 as Digit, 3 as Digit], &vec![3 as Digit])
         );
     }
 
     #[test]
-    fn test_result_negative_carrige_down_multiple() {
+    fn test_div_multiple_digit() {
+        //桁の基数をBとして (B^2 - 1) / (B + 1) = B - 1 余り 0
         assert_eq!(
-            (vec![1 as Digit], true),
-            arbitrary_precision_sub(
-                &vec![Digit::MAX, Digit::MAX],
-                &vec![0 as Digit, 0 as Digit, 1 as Digit]
-            )
+            (vec![Digit::MAX], vec![0 as Digit]),
+            arbitrary_precision_div_rem(&vec![Digit::MAX, Digit::MAX], &vec![1 as Digit, 1 as Digit])
         );
     }
 }
@@ -0,0 +1,449 @@
+/**複素数型を提供するモジュールです
+
+実部・虚部の型として`Integer`(ガウス整数)と`Rational`(ガウス有理数)を想定しています。
+四則演算は成分の型の演算のみを使って定義されるため、`Rational`の場合は厳密に計算されます。
+`Rational`の場合は、精度を指定して絶対値・偏角・指数関数・対数関数・平方根も計算できます。
+*/
+use std::fmt;
+use std::ops::*;
+use std::str::FromStr;
+
+use crate::fixed_point::*;
+use crate::functions::{atan2, exp_fixed, ln_fixed, sin_cos_rational_fixed};
+use crate::math_traits::*;
+use crate::num::{Integer, ParseNumberError, Rational, Sign};
+
+/// rfmライブラリにおける複素数型の表現です。
+/// Complex number expression in rfm library.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Complex<T> {
+    ///実部
+    pub re: T,
+    ///虚部
+    pub im: T,
+}
+
+impl<T> Complex<T> {
+    /// 実部と虚部から複素数を作成する
+    pub fn new(re: T, im: T) -> Complex<T> {
+        return Complex { re, im };
+    }
+}
+
+impl<T> Complex<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T>,
+    for<'a> &'a T: Add<&'a T, Output = T> + Mul<&'a T, Output = T> + Neg<Output = T>,
+{
+    /// 共役複素数を求める
+    pub fn conj(&self) -> Complex<T> {
+        return Complex::new(self.re.clone(), -&self.im);
+    }
+
+    /// ノルム(絶対値の2乗) re^2 + im^2 を求める
+    pub fn norm(&self) -> T {
+        return &(&self.re * &self.re) + &(&self.im * &self.im);
+    }
+
+    /// 正の指数による累乗(繰り返し2乗法)
+    fn pow_positive(&self, exp: u64) -> Complex<T> {
+        if exp == 1 {
+            return self.clone();
+        }
+        let half = self.pow_positive(exp / 2);
+        let squared = &half * &half;
+        return match exp % 2 {
+            0 => squared,
+            _ => &squared * self,
+        };
+    }
+}
+
+impl<T> Add<&Complex<T>> for &Complex<T>
+where
+    for<'a> &'a T: Add<&'a T, Output = T>,
+{
+    type Output = Complex<T>;
+    fn add(self, rhs: &Complex<T>) -> Self::Output {
+        return Complex::new(&self.re + &rhs.re, &self.im + &rhs.im);
+    }
+}
+
+impl<T> Add for Complex<T>
+where
+    for<'a> &'a T: Add<&'a T, Output = T>,
+{
+    type Output = Complex<T>;
+    fn add(self, rhs: Self) -> Self::Output {
+        return &self + &rhs;
+    }
+}
+
+impl<T> AddAssign for Complex<T>
+where
+    for<'a> &'a T: Add<&'a T, Output = T>,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        *self = &*self + &rhs;
+    }
+}
+
+impl<T> Sub<&Complex<T>> for &Complex<T>
+where
+    for<'a> &'a T: Sub<&'a T, Output = T>,
+{
+    type Output = Complex<T>;
+    fn sub(self, rhs: &Complex<T>) -> Self::Output {
+        return Complex::new(&self.re - &rhs.re, &self.im - &rhs.im);
+    }
+}
+
+impl<T> Sub for Complex<T>
+where
+    for<'a> &'a T: Sub<&'a T, Output = T>,
+{
+    type Output = Complex<T>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        return &self - &rhs;
+    }
+}
+
+impl<T> SubAssign for Complex<T>
+where
+    for<'a> &'a T: Sub<&'a T, Output = T>,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = &*self - &rhs;
+    }
+}
+
+impl<T> Mul<&Complex<T>> for &Complex<T>
+where
+    T: Add<Output = T> + Sub<Output = T>,
+    for<'a> &'a T: Mul<&'a T, Output = T>,
+{
+    type Output = Complex<T>;
+    fn mul(self, rhs: &Complex<T>) -> Self::Output {
+        return Complex::new(
+            &self.re * &rhs.re - &self.im * &rhs.im,
+            &self.re * &rhs.im + &self.im * &rhs.re,
+        );
+    }
+}
+
+impl<T> Mul for Complex<T>
+where
+    T: Add<Output = T> + Sub<Output = T>,
+    for<'a> &'a T: Mul<&'a T, Output = T>,
+{
+    type Output = Complex<T>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        return &self * &rhs;
+    }
+}
+
+impl<T> MulAssign for Complex<T>
+where
+    T: Add<Output = T> + Sub<Output = T>,
+    for<'a> &'a T: Mul<&'a T, Output = T>,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = &*self * &rhs;
+    }
+}
+
+impl<T> Div<&Complex<T>> for &Complex<T>
+where
+    T: Add<Output = T> + Sub<Output = T>,
+    for<'a> &'a T: Mul<&'a T, Output = T> + Div<&'a T, Output = T>,
+{
+    type Output = Complex<T>;
+    /// (a + bi) / (c + di) = ((ac + bd) + (bc - ad)i) / (c^2 + d^2)
+    ///
+    /// `Integer`の場合は各成分を0方向に切り捨てる。
+    fn div(self, rhs: &Complex<T>) -> Self::Output {
+        let norm = &rhs.re * &rhs.re + &rhs.im * &rhs.im;
+        let re = &self.re * &rhs.re + &self.im * &rhs.im;
+        let im = &self.im * &rhs.re - &self.re * &rhs.im;
+        return Complex::new(&re / &norm, &im / &norm);
+    }
+}
+
+impl<T> Div for Complex<T>
+where
+    T: Add<Output = T> + Sub<Output = T>,
+    for<'a> &'a T: Mul<&'a T, Output = T> + Div<&'a T, Output = T>,
+{
+    type Output = Complex<T>;
+    fn div(self, rhs: Self) -> Self::Output {
+        return &self / &rhs;
+    }
+}
+
+impl<T> DivAssign for Complex<T>
+where
+    T: Add<Output = T> + Sub<Output = T>,
+    for<'a> &'a T: Mul<&'a T, Output = T> + Div<&'a T, Output = T>,
+{
+    fn div_assign(&mut self, rhs: Self) {
+        *self = &*self / &rhs;
+    }
+}
+
+impl<T> Neg for &Complex<T>
+where
+    for<'a> &'a T: Neg<Output = T>,
+{
+    type Output = Complex<T>;
+    fn neg(self) -> Self::Output {
+        return Complex::new(-&self.re, -&self.im);
+    }
+}
+
+impl<T> Neg for Complex<T>
+where
+    for<'a> &'a T: Neg<Output = T>,
+{
+    type Output = Complex<T>;
+    fn neg(self) -> Self::Output {
+        return -&self;
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Complex<T> {
+    /// `3+4i`、`1/2-3/4i`の形式で出力する
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let im = self.im.to_string();
+        let (sign, im) = match im.strip_prefix('-') {
+            Some(magnitude) => ("-", magnitude.to_string()),
+            None => ("+", im),
+        };
+        return f.pad(&format!("{}{}{}i", self.re, sign, im));
+    }
+}
+
+impl<T> FromStr for Complex<T>
+where
+    T: FromStr<Err = ParseNumberError>,
+{
+    type Err = ParseNumberError;
+    /// `3+4i`、`-2i`、`i`、`5`、`1/2-3/4i`の形式を読み込む
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseNumberError::Empty);
+        }
+        let Some(imaginary) = s.strip_suffix('i') else {
+            return Ok(Complex::new(s.parse()?, "0".parse()?));
+        };
+        //実部と虚部の区切りとなる符号を探す(先頭および分母の符号は除く)
+        let bytes = imaginary.as_bytes();
+        let split = (1..bytes.len())
+            .rev()
+            .find(|i_| (bytes[*i_] == b'+' || bytes[*i_] == b'-') && bytes[*i_ - 1] != b'/');
+        let (re, im) = match split {
+            Some(position) => (&imaginary[..position], &imaginary[position..]),
+            None => ("0", imaginary),
+        };
+        let im = match im {
+            "" | "+" => "1",
+            "-" => "-1",
+            _ => im.strip_prefix('+').unwrap_or(im),
+        };
+        return Ok(Complex::new(re.parse()?, im.parse()?));
+    }
+}
+
+impl Complex<Integer> {
+    /// 累乗を求める
+    pub fn pow(&self, exp: u64) -> Complex<Integer> {
+        if exp == 0 {
+            return Complex::new(Integer::one(), Integer::zero());
+        }
+        return self.pow_positive(exp);
+    }
+}
+
+impl From<Complex<Integer>> for Complex<Rational> {
+    fn from(value: Complex<Integer>) -> Self {
+        return Complex::new(Rational::from(&value.re), Rational::from(&value.im));
+    }
+}
+
+impl Complex<Rational> {
+    /// 累乗を求める
+    /// 指数が負の場合は逆数の累乗となる。
+    /// # Panics
+    /// 0を負の指数で累乗した場合
+    pub fn pow(&self, exp: i64) -> Complex<Rational> {
+        let one = Rational::from(&Integer::one());
+        let zero = Rational::from(&Integer::zero());
+        if exp == 0 {
+            return Complex::new(one, zero);
+        }
+        let powered = self.pow_positive(exp.unsigned_abs());
+        return match exp > 0 {
+            true => powered,
+            false => &Complex::new(one, zero) / &powered,
+        };
+    }
+
+    /**
+    絶対値 |z|
+    # Arguments
+    * prec - 小数点以下の10進桁数
+    # Returns
+    |z|を小数点以下prec桁に丸めた値
+    */
+    pub fn abs(&self, prec: usize) -> Rational {
+        return crate::functions::sqrt(&self.norm(), prec);
+    }
+
+    /**
+    偏角 arg(z)
+    # Arguments
+    * prec - 小数点以下の10進桁数
+    # Returns
+    偏角を小数点以下prec桁に丸めた値(範囲は[-π, π]、arg(0) = 0)
+    */
+    pub fn arg(&self, prec: usize) -> Rational {
+        return atan2(&self.im, &self.re, prec);
+    }
+
+    /**
+    指数関数 exp(z) = exp(re)(cos(im) + i sin(im))
+    # Arguments
+    * prec - 小数点以下の10進桁数
+    # Returns
+    実部・虚部をそれぞれ小数点以下prec桁に丸めた値
+    */
+    pub fn exp(&self, prec: usize) -> Complex<Rational> {
+        let w = precision_bits(prec) + GUARD_BITS;
+        let magnitude = exp_fixed(&self.re, w);
+        //exp(re)の大きさの分だけ三角関数の精度を上げる
+        let extra = (magnitude.bit_length() as usize).saturating_sub(w) + GUARD_BITS;
+        let (sin, cos) = sin_cos_rational_fixed(&self.im, w + extra);
+        return Complex::new(
+            fixed_to_rational(&fixed_mul(&magnitude, &cos, w + extra), w, prec),
+            fixed_to_rational(&fixed_mul(&magnitude, &sin, w + extra), w, prec),
+        );
+    }
+
+    /**
+    主値の対数関数 log(z) = log|z| + i arg(z)
+    # Arguments
+    * prec - 小数点以下の10進桁数
+    # Returns
+    実部・虚部をそれぞれ小数点以下prec桁に丸めた値
+    # Panics
+    z = 0の場合
+    */
+    pub fn ln(&self, prec: usize) -> Complex<Rational> {
+        let w = precision_bits(prec) + GUARD_BITS;
+        if self.norm().positive.sign() == Sign::Zero {
+            panic!("Argument of ln must not be zero");
+        }
+        //log|z| = log(|z|^2) / 2
+        let re = ln_fixed(&self.norm(), w + 1);
+        return Complex::new(fixed_to_rational(&re, w + 2, prec), self.arg(prec));
+    }
+
+    /**
+    主値の平方根 sqrt(z)
+
+    実部が0以上となる方の平方根を返します。
+    # Arguments
+    * prec - 小数点以下の10進桁数
+    # Returns
+    実部・虚部をそれぞれ小数点以下prec桁に丸めた値
+    */
+    pub fn sqrt(&self, prec: usize) -> Complex<Rational> {
+        let w = precision_bits(prec) + GUARD_BITS;
+        //0付近の平方根で精度が半減するため、2倍の精度で計算する
+        let work = 2 * w;
+        let modulus = rational_to_fixed(&self.norm(), 2 * work).sqrt();
+        let re_abs = rational_to_fixed(&Rational::new(&self.re.positive.abs(), &self.re.divider), work);
+        //u = sqrt((|z| + |re|) / 2)
+        let u = (&(&modulus + &re_abs) << (work - 1)).sqrt();
+        if u == Integer::zero() {
+            let zero = Rational::from(&Integer::zero());
+            return Complex::new(zero.clone(), zero);
+        }
+        //v = |im| / 2u
+        let v = &(&self.im.positive.abs() << (2 * work)) / &(&(&u * &self.im.divider) << 1);
+        let (re, im) = match self.re.positive.sign() {
+            Sign::Negative => (v, u),
+            _ => (u, v),
+        };
+        let im = match self.im.positive.sign() {
+            Sign::Negative => -im,
+            _ => im,
+        };
+        return Complex::new(fixed_to_rational(&re, work, prec), fixed_to_rational(&im, work, prec));
+    }
+}
+
+#[cfg(test)]
+mod complex_test {
+    use super::Complex;
+    use crate::math_traits::FromPrimitiveNumber;
+    use crate::num::{Integer, Rational};
+
+    fn gaussian(s: &str) -> Complex<Integer> {
+        return s.parse().unwrap();
+    }
+
+    fn complex(s: &str) -> Complex<Rational> {
+        return s.parse().unwrap();
+    }
+
+    /// 各成分の差が10^-prec以下であることを確認する
+    fn assert_close(actual: &Complex<Rational>, expected: &str, prec: usize) {
+        let diff = actual - &complex(expected);
+        let tolerance: Rational = format!("1/1{}", "0".repeat(prec)).parse().unwrap();
+        assert!(diff.re <= tolerance && -&diff.re <= tolerance, "actual {} expected {}", actual, expected);
+        assert!(diff.im <= tolerance && -&diff.im <= tolerance, "actual {} expected {}", actual, expected);
+    }
+
+    #[test]
+    fn test_integer_arithmetic() {
+        let a = gaussian("3+4i");
+        let b = gaussian("1-2i");
+        assert_eq!(&a + &b, gaussian("4+2i"));
+        assert_eq!(&a - &b, gaussian("2+6i"));
+        assert_eq!(&a * &b, gaussian("11-2i"));
+        assert_eq!(a.conj(), gaussian("3-4i"));
+        assert_eq!(a.norm(), Integer::from_u128(25));
+        assert_eq!(b.pow(3), gaussian("-11+2i"));
+        assert_eq!(a.pow(0), gaussian("1"));
+    }
+
+    #[test]
+    fn test_rational_arithmetic() {
+        let a = complex("1/2+3i");
+        let b = complex("2-i");
+        assert_eq!(&a / &b, complex("-2/5+13/10i"));
+        assert_eq!(&(&a / &b) * &b, a);
+        assert_eq!(b.pow(-2), complex("3/25+4/25i"));
+    }
+
+    #[test]
+    fn test_string() {
+        assert_eq!(gaussian("3+4i").to_string(), "3+4i");
+        assert_eq!(gaussian("-3-4i").to_string(), "-3-4i");
+        assert_eq!(gaussian("-i"), Complex::new(Integer::from_u128(0), Integer::from_i128(-1)));
+        assert_eq!(gaussian("7"), Complex::new(Integer::from_u128(7), Integer::from_u128(0)));
+        assert_eq!(complex("1/2-3/4i").to_string(), "1/2-3/4i");
+        assert!("3+4j".parse::<Complex<Integer>>().is_err());
+    }
+
+    #[test]
+    fn test_float_functions() {
+        let z = complex("1+2i");
+        assert_eq!(complex("3+4i").abs(20), "5".parse().unwrap());
+        assert_close(&Complex::new(z.arg(40), "0".parse().unwrap()), "1.1071487177940905030170654601785370400700", 40);
+        assert_close(&z.exp(40), "-1.1312043837568136384312552555107947106288+2.4717266720048189276169308935516645327361i", 40);
+        assert_close(&z.ln(40), "0.8047189562170501873003796666130938197628+1.1071487177940905030170654601785370400700i", 40);
+        assert_close(&z.sqrt(40), "1.2720196495140689642524224617374914917156+0.7861513777574232860695585858429589295231i", 40);
+        assert_close(&complex("-4").sqrt(40), "0+2i", 40);
+    }
+}
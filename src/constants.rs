@@ -0,0 +1,302 @@
+/**数学定数を任意精度で計算するモジュールです
+
+各定数は小数点以下の10進桁数を指定して求め、`10^-prec`に丸めた有理数で返します。
+戻り値と真の値との差の絶対値は`10^-prec`以下となります。
+
+一度計算した値は精度ごとにキャッシュされ、同じ精度での2回目以降の呼び出しは計算を省略します。
+また、より高い精度で計算済みの場合は、その値を切り捨てて低い精度の値を求めます。
+*/
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use crate::algorithm::binary_splitting;
+use crate::fixed_point::*;
+use crate::math_traits::*;
+use crate::num::{Integer, Rational};
+
+/// 定数ごとの計算結果のキャッシュ
+struct ConstantCache {
+    /// これまでに計算した最も精度の高い固定小数点数(bit数, 値)
+    fixed: Option<(usize, Integer)>,
+    /// 10進桁数ごとに丸めた値
+    rounded: BTreeMap<usize, Rational>,
+}
+
+impl ConstantCache {
+    const fn new() -> ConstantCache {
+        return ConstantCache {
+            fixed: None,
+            rounded: BTreeMap::new(),
+        };
+    }
+}
+
+static PI_CACHE: Mutex<ConstantCache> = Mutex::new(ConstantCache::new());
+static E_CACHE: Mutex<ConstantCache> = Mutex::new(ConstantCache::new());
+static LN2_CACHE: Mutex<ConstantCache> = Mutex::new(ConstantCache::new());
+static EULER_GAMMA_CACHE: Mutex<ConstantCache> = Mutex::new(ConstantCache::new());
+static SQRT2_CACHE: Mutex<ConstantCache> = Mutex::new(ConstantCache::new());
+static GOLDEN_RATIO_CACHE: Mutex<ConstantCache> = Mutex::new(ConstantCache::new());
+
+/// キャッシュを利用して定数の固定小数点数を求める
+fn cached_fixed(cache: &Mutex<ConstantCache>, w: usize, compute: fn(usize) -> Integer) -> Integer {
+    if let Some((bits, value)) = &cache.lock().unwrap().fixed {
+        if *bits >= w {
+            return value >> (bits - w);
+        }
+    }
+    //計算中はロックを保持しない
+    let value = compute(w);
+    let mut cache = cache.lock().unwrap();
+    if cache.fixed.as_ref().is_none_or(|(bits, _)| *bits < w) {
+        cache.fixed = Some((w, value.clone()));
+    }
+    return value;
+}
+
+/// キャッシュを利用して定数を小数点以下prec桁に丸めた値を求める
+fn cached_rational(cache: &Mutex<ConstantCache>, prec: usize, compute: fn(usize) -> Integer) -> Rational {
+    if let Some(value) = cache.lock().unwrap().rounded.get(&prec) {
+        return value.clone();
+    }
+    let w = precision_bits(prec) + GUARD_BITS;
+    let value = fixed_to_rational(&cached_fixed(cache, w, compute), w, prec);
+    cache.lock().unwrap().rounded.insert(prec, value.clone());
+    return value;
+}
+
+/// 円周率をChudnovskyの公式で求める
+fn compute_pi(w: usize) -> Integer {
+    //640320^3 / 24
+    const C3_OVER_24: u128 = 10939058860032000;
+    let work = w + GUARD_BITS;
+    //1項あたり約47.11bit精度が上がる
+    let terms = (work as u128) / 47 + 2;
+    let (_, q, t) = binary_splitting(
+        0,
+        terms,
+        &|n| match n {
+            0 => Integer::one(),
+            _ => -Integer::from_u128((6 * n - 5) * (2 * n - 1) * (6 * n - 1)),
+        },
+        &|n| match n {
+            0 => Integer::one(),
+            _ => &Integer::from_u128(n * n * n) * &Integer::from_u128(C3_OVER_24),
+        },
+        &|n| Integer::from_u128(13591409 + 545140134 * n),
+    );
+    let sqrt_10005 = (Integer::from_u128(10005) << (2 * work)).sqrt();
+    let pi = &(&(&q * &Integer::from_u128(426880)) * &sqrt_10005) / &t;
+    return pi >> GUARD_BITS;
+}
+
+/// 自然対数の底eを級数 e = Σ 1/k! で求める
+fn compute_e(w: usize) -> Integer {
+    let work = w + GUARD_BITS;
+    //k! > 2^work となるまで項を取る
+    let mut terms: u128 = 1;
+    let mut factorial_bits: usize = 0;
+    while factorial_bits <= work {
+        terms += 1;
+        factorial_bits += (u128::BITS - terms.leading_zeros() - 1) as usize;
+    }
+    let (_, q, t) = binary_splitting(
+        0,
+        terms + 1,
+        &|_| Integer::one(),
+        &|n| Integer::from_u128(n.max(1)),
+        &|_| Integer::one(),
+    );
+    return (&(t << work) / &q) >> GUARD_BITS;
+}
+
+/// log(2)をMachin型の公式で求める
+/// log(2) = 18 artanh(1/26) - 2 artanh(1/4801) + 8 artanh(1/8749)
+fn compute_ln2(w: usize) -> Integer {
+    let work = w + GUARD_BITS;
+    let one = Integer::one();
+    let term = |q: u128| atanh_fixed(&one, &Integer::from_u128(q), work);
+    let result = &(&(&term(26) * &Integer::from_u128(18)) - &(&term(4801) << 1)) + &(&term(8749) << 3);
+    return result >> GUARD_BITS;
+}
+
+/// 正の整数nの自然対数を求める
+/// n = 2^m y (1 <= y < 2)と分解し、log(y) = 2 artanh((y - 1) / (y + 1))を使う
+fn ln_integer_fixed(n: &Integer, w: usize) -> Integer {
+    let m = n.bit_length() as usize - 1;
+    let power = Integer::one() << m;
+    let fraction = atanh_fixed(&(n - &power), &(n + &power), w) << 1;
+    return &(&ln2_fixed(w) * &Integer::from_u128(m as u128)) + &fraction;
+}
+
+/**オイラーの定数γをBrent-McMillanのアルゴリズムで求める
+
+γ ≒ U/V、ただし
+U = Σ (n^k/k!)^2 (H_k - log(n))、V = Σ (n^k/k!)^2
+であり、誤差はおおよそexp(-4n)となる。
+*/
+fn compute_euler_gamma(w: usize) -> Integer {
+    let work = w + 2 * GUARD_BITS;
+    //exp(-4n) < 2^-work となるようにnを選ぶ(log(2)/4 < 0.174)
+    let n = Integer::from_u128((work as u128) * 174 / 1000 + 1);
+    let n_square = &n * &n;
+    let mut a = -ln_integer_fixed(&n, work);
+    let mut b = Integer::one() << work;
+    let mut u = a.clone();
+    let mut v = b.clone();
+    let mut index: u128 = 1;
+    while b != Integer::zero() || a != Integer::zero() {
+        let k = Integer::from_u128(index);
+        let k_square = &k * &k;
+        b = &(&b * &n_square) / &k_square;
+        a = &(&(&(&a * &n_square) / &k) + &b) / &k;
+        u += a.clone();
+        v += b.clone();
+        index += 1;
+    }
+    return fixed_div(&u, &v, work) >> (2 * GUARD_BITS);
+}
+
+/// 2の平方根を求める
+fn compute_sqrt2(w: usize) -> Integer {
+    return (Integer::from_u128(2) << (2 * w)).sqrt();
+}
+
+/// 黄金比(1 + sqrt(5)) / 2を求める
+fn compute_golden_ratio(w: usize) -> Integer {
+    let work = w + 1;
+    let sqrt5 = (Integer::from_u128(5) << (2 * work)).sqrt();
+    return (&(Integer::one() << work) + &sqrt5) >> 2;
+}
+
+/// 円周率πの固定小数点数(内部用)
+pub(crate) fn pi_fixed(w: usize) -> Integer {
+    return cached_fixed(&PI_CACHE, w, compute_pi);
+}
+
+/// log(2)の固定小数点数(内部用)
+pub(crate) fn ln2_fixed(w: usize) -> Integer {
+    return cached_fixed(&LN2_CACHE, w, compute_ln2);
+}
+
+/**円周率π
+# Arguments
+* prec - 小数点以下の10進桁数
+# Returns
+πを小数点以下prec桁に丸めた値
+*/
+pub fn pi(prec: usize) -> Rational {
+    return cached_rational(&PI_CACHE, prec, compute_pi);
+}
+
+/**自然対数の底e
+# Arguments
+* prec - 小数点以下の10進桁数
+# Returns
+eを小数点以下prec桁に丸めた値
+*/
+pub fn e(prec: usize) -> Rational {
+    return cached_rational(&E_CACHE, prec, compute_e);
+}
+
+/**2の自然対数log(2)
+# Arguments
+* prec - 小数点以下の10進桁数
+# Returns
+log(2)を小数点以下prec桁に丸めた値
+*/
+pub fn ln2(prec: usize) -> Rational {
+    return cached_rational(&LN2_CACHE, prec, compute_ln2);
+}
+
+/**オイラーの定数γ
+# Arguments
+* prec - 小数点以下の10進桁数
+# Returns
+γを小数点以下prec桁に丸めた値
+*/
+pub fn euler_gamma(prec: usize) -> Rational {
+    return cached_rational(&EULER_GAMMA_CACHE, prec, compute_euler_gamma);
+}
+
+/**2の平方根
+# Arguments
+* prec - 小数点以下の10進桁数
+# Returns
+sqrt(2)を小数点以下prec桁に丸めた値
+*/
+pub fn sqrt2(prec: usize) -> Rational {
+    return cached_rational(&SQRT2_CACHE, prec, compute_sqrt2);
+}
+
+/**黄金比(1 + sqrt(5)) / 2
+# Arguments
+* prec - 小数点以下の10進桁数
+# Returns
+黄金比を小数点以下prec桁に丸めた値
+*/
+pub fn golden_ratio(prec: usize) -> Rational {
+    return cached_rational(&GOLDEN_RATIO_CACHE, prec, compute_golden_ratio);
+}
+
+#[cfg(test)]
+mod constants_test {
+    use super::*;
+
+    /// 期待値の先頭prec桁と一致することを確認する
+    fn assert_digits(actual: Rational, expected: &str, prec: usize) {
+        let point = expected.find('.').unwrap();
+        let expected: Rational = expected[..point + prec + 1].parse().unwrap();
+        let tolerance = Rational::new(
+            &Integer::one(),
+            &Integer::from_u128(10).pow(Integer::from_u128(prec as u128)),
+        );
+        let diff = &actual - &expected;
+        let diff = Rational::new(&diff.positive.abs(), &diff.divider);
+        assert!(diff <= tolerance, "actual {} expected {}", actual, expected);
+    }
+
+    const PI: &str = "3.14159265358979323846264338327950288419716939937510582097494459230781640628620899862803482534211706798214808651328230664709384460955058223172535940812848111745028410270193852110555964462294895493038196";
+    const E: &str = "2.71828182845904523536028747135266249775724709369995957496696762772407663035354759457138217852516642742746639193200305992181741359662904357290033429526059563073813232862794349076323382988075319525101901";
+    const LN2: &str = "0.69314718055994530941723212145817656807550013436025525412068000949339362196969471560586332699641868754200148102057068573368552023575813055703267075163507596193072757082837143519030703862389167347112335";
+    const EULER_GAMMA: &str = "0.57721566490153286060651209008240243104215933593992359880576723488486772677766467093694706329174674951463144724980708248096050401448654283622417399764492353625350033374293733773767394279259525824709491";
+    const SQRT2: &str = "1.41421356237309504880168872420969807856967187537694807317667973799073247846210703885038753432764157273501384623091229702492483605585073721264412149709993583141322266592750559275579995050115278206057147";
+    const GOLDEN_RATIO: &str = "1.61803398874989484820458683436563811772030917980576286213544862270526046281890244970720720418939113748475408807538689175212663386222353693179318006076672635443338908659593958290563832266131992829026788";
+
+    #[test]
+    fn test_pi() {
+        assert_digits(pi(200), PI, 200);
+        assert_digits(pi(1), PI, 1);
+    }
+
+    #[test]
+    fn test_e() {
+        assert_digits(e(200), E, 200);
+    }
+
+    #[test]
+    fn test_ln2() {
+        assert_digits(ln2(200), LN2, 200);
+    }
+
+    #[test]
+    fn test_euler_gamma() {
+        assert_digits(euler_gamma(200), EULER_GAMMA, 200);
+    }
+
+    #[test]
+    fn test_sqrt2_golden_ratio() {
+        assert_digits(sqrt2(200), SQRT2, 200);
+        assert_digits(golden_ratio(200), GOLDEN_RATIO, 200);
+    }
+
+    #[test]
+    fn test_cache() {
+        //高い精度の値を切り捨てて求めても同じ結果となる
+        let high = sqrt2(120);
+        let low = sqrt2(30);
+        assert_eq!(low, sqrt2(30));
+        assert_digits(high, SQRT2, 120);
+        assert_digits(low, SQRT2, 30);
+    }
+}
@@ -0,0 +1,228 @@
+//! 連分数と最良近似
+//!
+//! 有理数と単純連分数 `a0 + 1/(a1 + 1/(a2 + ...))` の相互変換、近似分数(convergent)の列挙、
+//! 分母に上限を設けた最良近似、区間内で最も単純な分数(Stern–Brocot木)を扱います。
+//! 連分数の先頭の項は床関数で求めるため負になりえますが、2項目以降は常に正です。
+use crate::fixed_point::floor_div;
+use crate::math_traits::{One, Zero};
+use crate::num::{Integer, Rational, Sign};
+
+/// 有理数の単純連分数の項を先頭から順に返すイテレータ
+#[derive(Debug, Clone)]
+pub struct ContinuedFraction {
+    numerator: Integer,
+    denominator: Integer,
+}
+
+impl Iterator for ContinuedFraction {
+    type Item = Integer;
+    fn next(&mut self) -> Option<Integer> {
+        if self.denominator.sign() == Sign::Zero {
+            return None;
+        }
+        let term = floor_div(&self.numerator, &self.denominator);
+        let remain = &self.numerator - &(&term * &self.denominator);
+        self.numerator = std::mem::replace(&mut self.denominator, remain);
+        return Some(term);
+    }
+}
+
+/// 連分数の項から近似分数を順に返すイテレータ
+///
+/// n番目の近似分数は先頭からn+1項で打ち切った連分数の値です。
+#[derive(Debug, Clone)]
+pub struct Convergents<I> {
+    terms: I,
+    //直前2つの近似分数の分子と分母
+    previous: (Integer, Integer),
+    current: (Integer, Integer),
+}
+
+impl<I: Iterator<Item = Integer>> Convergents<I> {
+    /// 連分数の項の列から作成する
+    pub fn new<T: IntoIterator<IntoIter = I>>(terms: T) -> Convergents<I> {
+        return Convergents {
+            terms: terms.into_iter(),
+            previous: (Integer::zero(), Integer::one()),
+            current: (Integer::one(), Integer::zero()),
+        };
+    }
+}
+
+impl<I: Iterator<Item = Integer>> Iterator for Convergents<I> {
+    type Item = Rational;
+    fn next(&mut self) -> Option<Rational> {
+        let term = self.terms.next()?;
+        let next = (
+            &(&term * &self.current.0) + &self.previous.0,
+            &(&term * &self.current.1) + &self.previous.1,
+        );
+        self.previous = std::mem::replace(&mut self.current, next);
+        return Some(Rational::new(&self.current.0, &self.current.1));
+    }
+}
+
+impl Rational {
+    /// 単純連分数の項を先頭から返すイテレータを作成する
+    pub fn continued_fraction(&self) -> ContinuedFraction {
+        return ContinuedFraction {
+            numerator: self.positive.clone(),
+            denominator: self.divider.clone(),
+        };
+    }
+
+    /// 近似分数を粗いものから順に返すイテレータを作成する(最後の値は自身と等しい)
+    pub fn convergents(&self) -> Convergents<ContinuedFraction> {
+        return Convergents::new(self.continued_fraction());
+    }
+
+    /**
+    単純連分数の項から有理数を作成する
+    # Panics
+    項が空の場合、または途中で0除算となる場合(2項目以降に0や負の値を含む場合など)
+     */
+    pub fn from_continued_fraction<T: IntoIterator<Item = Integer>>(terms: T) -> Rational {
+        let terms: Vec<Integer> = terms.into_iter().collect();
+        let (last, rest) = terms.split_last().expect("empty continued fraction");
+        //末尾から畳み込む
+        let (mut numerator, mut denominator) = (last.clone(), Integer::one());
+        for term in rest.iter().rev() {
+            let next = &(term * &numerator) + &denominator;
+            denominator = numerator;
+            numerator = next;
+        }
+        return Rational::new(&numerator, &denominator);
+    }
+
+    /**
+    分母がmax以下の分数のうち、自身に最も近いものを求める
+    # Arguments
+    * max - 分母の上限(1以上)
+    # Returns
+    距離が等しい候補が2つある場合は、分母の小さい方
+    # Panics
+    maxが1未満の場合
+     */
+    pub fn limit_denominator(&self, max: &Integer) -> Rational {
+        assert!(*max >= Integer::one(), "max denominator must be at least 1");
+        if self.divider <= *max {
+            return self.clone();
+        }
+        let (mut p0, mut q0, mut p1, mut q1) = (Integer::zero(), Integer::one(), Integer::one(), Integer::zero());
+        let (mut numerator, mut denominator) = (self.positive.clone(), self.divider.clone());
+        loop {
+            let term = floor_div(&numerator, &denominator);
+            let q2 = &q0 + &(&term * &q1);
+            if q2 > *max {
+                break;
+            }
+            let p2 = &p0 + &(&term * &p1);
+            (p0, q0, p1, q1) = (p1, q1, p2, q2);
+            let remain = &numerator - &(&term * &denominator);
+            numerator = std::mem::replace(&mut denominator, remain);
+        }
+        //最後の近似分数と、その手前の中間近似分数のうち近い方を選ぶ
+        let k = floor_div(&(max - &q0), &q1);
+        let semiconvergent = Rational::new(&(&p0 + &(&k * &p1)), &(&q0 + &(&k * &q1)));
+        let convergent = Rational::new(&p1, &q1);
+        let distance = |value: &Rational| {
+            let difference = value - self;
+            Rational::new(&difference.positive.abs(), &difference.divider)
+        };
+        if distance(&convergent) <= distance(&semiconvergent) {
+            return convergent;
+        }
+        return semiconvergent;
+    }
+
+    /**
+    閉区間[a, b](またはa > bのとき[b, a])に含まれる最も単純な分数を求める
+
+    最も単純な分数とは、分母が最小で、その中で分子の絶対値が最小のもの(Stern–Brocot木で根に最も近いもの)です。
+     */
+    pub fn simplest_between(a: &Rational, b: &Rational) -> Rational {
+        let (low, high) = match a <= b {
+            true => (a.clone(), b.clone()),
+            false => (b.clone(), a.clone()),
+        };
+        if low.positive.sign() != Sign::Positive && high.positive.sign() != Sign::Negative {
+            return Rational::zero();
+        }
+        if high.positive.sign() == Sign::Negative {
+            return -Rational::simplest_between(&-&high, &-&low);
+        }
+        //0 < low <= high: 整数部分が一致する間は連分数の項を取り出し、残りの区間の逆数をとって続ける
+        let (mut low, mut high) = (low, high);
+        let mut terms: Vec<Integer> = Vec::new();
+        loop {
+            let floor_low = floor_div(&low.positive, &low.divider);
+            if low.divider == Integer::one() {
+                terms.push(floor_low);
+                break;
+            }
+            if floor_low < floor_div(&high.positive, &high.divider) {
+                terms.push(floor_low + Integer::one());
+                break;
+            }
+            let integer_part = Rational::from(&floor_low);
+            (low, high) = (
+                Rational::one() / (&high - &integer_part),
+                Rational::one() / (&low - &integer_part),
+            );
+            terms.push(floor_low);
+        }
+        return Rational::from_continued_fraction(terms);
+    }
+}
+
+#[cfg(test)]
+mod continued_fraction_test {
+    use crate::math_traits::FromPrimitiveNumber;
+    use crate::num::{Integer, Rational};
+
+    fn rational(s: &str) -> Rational {
+        return s.parse().unwrap();
+    }
+
+    fn integers(values: &[i128]) -> Vec<Integer> {
+        return values.iter().map(|value| Integer::from_i128(*value)).collect();
+    }
+
+    #[test]
+    fn round_trip_test() {
+        assert_eq!(rational("415/93").continued_fraction().collect::<Vec<_>>(), integers(&[4, 2, 6, 7]));
+        assert_eq!(rational("-7/3").continued_fraction().collect::<Vec<_>>(), integers(&[-3, 1, 2]));
+        assert_eq!(rational("0").continued_fraction().collect::<Vec<_>>(), integers(&[0]));
+        assert_eq!(Rational::from_continued_fraction(integers(&[4, 2, 6, 7])), rational("415/93"));
+        assert_eq!(Rational::from_continued_fraction(integers(&[-3, 1, 2])), rational("-7/3"));
+    }
+
+    #[test]
+    fn convergents_test() {
+        let convergents: Vec<Rational> = rational("3.14159265358979").convergents().take(4).collect();
+        assert_eq!(convergents, vec![rational("3"), rational("22/7"), rational("333/106"), rational("355/113")]);
+        let last = rational("415/93").convergents().last().unwrap();
+        assert_eq!(last, rational("415/93"));
+    }
+
+    #[test]
+    fn limit_denominator_test() {
+        let pi = rational("3.141592653589793");
+        assert_eq!(pi.limit_denominator(&Integer::from_i128(10)), rational("22/7"));
+        assert_eq!(pi.limit_denominator(&Integer::from_i128(100)), rational("311/99"));
+        assert_eq!(pi.limit_denominator(&Integer::from_i128(1000)), rational("355/113"));
+        assert_eq!(rational("-0.3333").limit_denominator(&Integer::from_i128(5)), rational("-1/3"));
+        assert_eq!(rational("5/3").limit_denominator(&Integer::from_i128(3)), rational("5/3"));
+        assert_eq!(rational("1/2").limit_denominator(&Integer::from_i128(1)), rational("0"));
+    }
+
+    #[test]
+    fn simplest_between_test() {
+        assert_eq!(Rational::simplest_between(&rational("0.33"), &rational("0.34")), rational("1/3"));
+        assert_eq!(Rational::simplest_between(&rational("1/2"), &rational("1/3")), rational("1/2"));
+        assert_eq!(Rational::simplest_between(&rational("-0.34"), &rational("-0.33")), rational("-1/3"));
+        assert_eq!(Rational::simplest_between(&rational("-1/2"), &rational("3")), rational("0"));
+        assert_eq!(Rational::simplest_between(&rational("2.5"), &rational("3.7")), rational("3"));
+        assert_eq!(Rational::simplest_between(&rational("3.1415"), &rational("3.1416")), rational("333/106"));
+    }
+}
@@ -0,0 +1,182 @@
+//! 整数の絶対値を保持する桁の列
+//!
+//! 多くの整数は256bit以内に収まるため、`INLINE_DIGITS`桁まではヒープを使わずに値の内部へ直接保持し、
+//! それを超えた時点でヒープ上の`Vec`へ移し替えます。
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use crate::num::Digit;
+
+/// ヒープを使わずに保持できる桁数(桁の幅によらず256bit分)
+pub(crate) const INLINE_DIGITS: usize = 256 / Digit::BITS as usize;
+
+/// 少ない桁数を内部に直接保持する桁の列
+#[derive(Clone)]
+pub(crate) enum DigitVec {
+    ///INLINE_DIGITS桁以下の値
+    Inline { len: usize, data: [Digit; INLINE_DIGITS] },
+    ///INLINE_DIGITS桁を超えたことのある値
+    Heap(Vec<Digit>),
+}
+
+impl DigitVec {
+    /// 空の列を作成する
+    pub(crate) const fn new() -> DigitVec {
+        return DigitVec::Inline {
+            len: 0,
+            data: [0; INLINE_DIGITS],
+        };
+    }
+
+    /// スライスの内容を複製して作成する
+    pub(crate) fn from_slice(digits: &[Digit]) -> DigitVec {
+        let mut result = DigitVec::new();
+        result.extend_from_slice(digits);
+        return result;
+    }
+
+    /// ヒープ上に保持しているか
+    #[cfg(test)]
+    pub(crate) fn spilled(&self) -> bool {
+        return matches!(self, DigitVec::Heap(_));
+    }
+
+    /// 内部に収まらなくなったらヒープへ移し替える
+    fn reserve_for(&mut self, new_len: usize) -> Option<&mut Vec<Digit>> {
+        if let DigitVec::Inline { len, data } = self {
+            if new_len <= INLINE_DIGITS {
+                return None;
+            }
+            let mut heap = Vec::with_capacity(new_len);
+            heap.extend_from_slice(&data[..*len]);
+            *self = DigitVec::Heap(heap);
+        }
+        return match self {
+            DigitVec::Heap(heap) => Some(heap),
+            DigitVec::Inline { .. } => None,
+        };
+    }
+
+    /// 末尾に1桁追加する
+    pub(crate) fn push(&mut self, digit: Digit) {
+        let new_len = self.len() + 1;
+        match self.reserve_for(new_len) {
+            Some(heap) => heap.push(digit),
+            None => {
+                if let DigitVec::Inline { len, data } = self {
+                    data[*len] = digit;
+                    *len += 1;
+                }
+            }
+        }
+    }
+
+    /// 先頭からlength桁だけを残す
+    pub(crate) fn truncate(&mut self, length: usize) {
+        match self {
+            DigitVec::Heap(heap) => heap.truncate(length),
+            DigitVec::Inline { len, .. } => *len = (*len).min(length),
+        }
+    }
+
+    /// 桁数をnew_lenに変更し、増えた桁はvalueで埋める
+    pub(crate) fn resize(&mut self, new_len: usize, value: Digit) {
+        match self.reserve_for(new_len) {
+            Some(heap) => heap.resize(new_len, value),
+            None => {
+                if let DigitVec::Inline { len, data } = self {
+                    if new_len > *len {
+                        data[*len..new_len].fill(value);
+                    }
+                    *len = new_len;
+                }
+            }
+        }
+    }
+
+    /// 全ての桁を取り除く(確保済みの領域は保持する)
+    pub(crate) fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// 末尾にスライスの内容を追加する
+    pub(crate) fn extend_from_slice(&mut self, digits: &[Digit]) {
+        let old_len = self.len();
+        match self.reserve_for(old_len + digits.len()) {
+            Some(heap) => heap.extend_from_slice(digits),
+            None => {
+                if let DigitVec::Inline { len, data } = self {
+                    data[old_len..old_len + digits.len()].copy_from_slice(digits);
+                    *len += digits.len();
+                }
+            }
+        }
+    }
+}
+
+impl Deref for DigitVec {
+    type Target = [Digit];
+    fn deref(&self) -> &[Digit] {
+        return match self {
+            DigitVec::Inline { len, data } => &data[..*len],
+            DigitVec::Heap(heap) => heap,
+        };
+    }
+}
+
+impl DerefMut for DigitVec {
+    fn deref_mut(&mut self) -> &mut [Digit] {
+        return match self {
+            DigitVec::Inline { len, data } => &mut data[..*len],
+            DigitVec::Heap(heap) => heap,
+        };
+    }
+}
+
+impl From<Vec<Digit>> for DigitVec {
+    /// 内部に収まる場合はヒープ領域を手放す
+    fn from(digits: Vec<Digit>) -> Self {
+        if digits.len() <= INLINE_DIGITS {
+            return DigitVec::from_slice(&digits);
+        }
+        return DigitVec::Heap(digits);
+    }
+}
+
+impl PartialEq for DigitVec {
+    fn eq(&self, other: &Self) -> bool {
+        return **self == **other;
+    }
+}
+
+impl Eq for DigitVec {}
+
+impl fmt::Debug for DigitVec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return f.debug_list().entries(self.iter()).finish();
+    }
+}
+
+#[cfg(test)]
+mod digits_test {
+    use super::{DigitVec, INLINE_DIGITS};
+    use crate::num::Digit;
+
+    #[test]
+    fn spill_test() {
+        let mut digits = DigitVec::from_slice(&[1]);
+        assert!(!digits.spilled());
+        for i_ in 1..INLINE_DIGITS {
+            digits.push(i_ as Digit + 1);
+        }
+        assert!(!digits.spilled());
+        digits.push(9);
+        assert!(digits.spilled());
+        assert_eq!(digits.len(), INLINE_DIGITS + 1);
+        digits.resize(INLINE_DIGITS + 3, 0);
+        digits.truncate(1);
+        assert_eq!(&digits[..], &[1]);
+        assert_eq!(DigitVec::from(vec![1, 2, 3]), DigitVec::from_slice(&[1, 2, 3]));
+        assert!(!DigitVec::from(vec![7]).spilled());
+    }
+}
@@ -0,0 +1,62 @@
+/**固定小数点数による近似計算の共通処理
+
+実数xを`2^w`倍して整数に丸めた値(固定小数点数)で扱う。
+初等関数や数学定数の計算で共通して使用する。
+*/
+use crate::math_traits::*;
+use crate::num::{Integer, Rational};
+
+/// 丸め誤差を吸収するために余分に確保するbit数
+pub(crate) const GUARD_BITS: usize = 64;
+
+/// 10進数の桁数を2進数のbit数に換算する(log2(10) < 3.322)
+pub(crate) fn precision_bits(prec: usize) -> usize {
+    return prec * 3322 / 1000 + 1;
+}
+
+/// 有理数を固定小数点数に変換する(0方向に切り捨て)
+pub(crate) fn rational_to_fixed(x: &Rational, w: usize) -> Integer {
+    return &(&x.positive << w) / &x.divider;
+}
+
+/// 固定小数点数を小数点以下prec桁に四捨五入した有理数に変換する
+pub(crate) fn fixed_to_rational(value: &Integer, w: usize, prec: usize) -> Rational {
+    let scale = Integer::from_u128(10).pow(Integer::from_u128(prec as u128));
+    let half = Integer::one() << (w - 1);
+    let rounded = &(&(value * &scale) + &half) >> w;
+    return Rational::new(&rounded, &scale);
+}
+
+/// 固定小数点数同士の乗算
+pub(crate) fn fixed_mul(lhs: &Integer, rhs: &Integer, w: usize) -> Integer {
+    return &(lhs * rhs) >> w;
+}
+
+/// 固定小数点数同士の除算
+pub(crate) fn fixed_div(lhs: &Integer, rhs: &Integer, w: usize) -> Integer {
+    return &(lhs << w) / rhs;
+}
+
+/// 床関数付きの除算(負の無限大方向に丸める)
+pub(crate) fn floor_div(lhs: &Integer, rhs: &Integer) -> Integer {
+    let (quotient, remain) = lhs.div_rem(rhs);
+    if remain != Integer::zero() && remain.sign() != rhs.sign() {
+        return &quotient - &Integer::one();
+    }
+    return quotient;
+}
+
+/// |p/q| < 1に対してartanh(p/q)を級数展開で求める
+pub(crate) fn atanh_fixed(p: &Integer, q: &Integer, w: usize) -> Integer {
+    let p_square = p * p;
+    let q_square = q * q;
+    let mut power = &(p << w) / q;
+    let mut result = Integer::zero();
+    let mut index: u128 = 0;
+    while power != Integer::zero() {
+        result += &power / &Integer::from_u128(2 * index + 1);
+        power = &(&power * &p_square) / &q_square;
+        index += 1;
+    }
+    return result;
+}
@@ -0,0 +1,518 @@
+/**初等関数(指数・対数・平方根・三角関数・双曲線関数)を任意精度で計算するモジュールです
+
+引数は有理数で与え、結果は指定された精度(小数点以下の10進桁数)に丸めた有理数で返します。
+すべての関数で、戻り値と真の値との差の絶対値は`10^-prec`以下となります。
+
+内部では`2^w`倍した整数(固定小数点数)で計算します。
+*/
+use crate::constants::{ln2_fixed, pi_fixed};
+use crate::fixed_point::*;
+use crate::math_traits::*;
+use crate::num::{Integer, Rational, Sign};
+
+/// 有理数の絶対値の整数部分のおおよそのbit数(上から評価)
+fn magnitude_bits(x: &Rational) -> usize {
+    let bits = x.positive.bit_length() as i64 - x.divider.bit_length() as i64 + 1;
+    return bits.max(0) as usize;
+}
+
+/// |r| < 1の範囲でexp(r)をテイラー展開で求める
+fn exp_small_fixed(r: &Integer, w: usize) -> Integer {
+    let mut term = Integer::one() << w;
+    let mut result = term.clone();
+    let mut index: u128 = 1;
+    loop {
+        term = &fixed_mul(&term, r, w) / &Integer::from_u128(index);
+        if term == Integer::zero() {
+            return result;
+        }
+        result += term.clone();
+        index += 1;
+    }
+}
+
+/**exp(x)を固定小数点数で求める
+
+x = k log(2) + r (|r| < log(2))と分解し、exp(x) = 2^k exp(r)として計算する。
+*/
+pub(crate) fn exp_fixed(x: &Rational, w: usize) -> Integer {
+    if x.positive == Integer::zero() {
+        return Integer::one() << w;
+    }
+    let k = &rational_to_fixed(x, GUARD_BITS) / &ln2_fixed(GUARD_BITS);
+    let k = match k.to_i128() {
+        Some(value) => value,
+        None if k.sign() == Sign::Negative => return Integer::zero(),
+        None => panic!("Argument of exp is too large"),
+    };
+    if k < 0 && (-k) as usize > w + GUARD_BITS {
+        //結果が精度以下になるので0とみなせる
+        return Integer::zero();
+    }
+    //2^k倍されるので、その分だけrの精度を上げる
+    let work = w + k.max(0) as usize + GUARD_BITS;
+    let wide = work + 128 + GUARD_BITS;
+    let r = &(&rational_to_fixed(x, wide) - &(&Integer::from_i128(k) * &ln2_fixed(wide))) >> (wide - work);
+    let exp_r = exp_small_fixed(&r, work);
+    return exp_r >> (GUARD_BITS + (-k).max(0) as usize);
+}
+
+/**log(x)を固定小数点数で求める
+
+x = 2^m y (1/2 < y < 2)と分解し、log(y) = 2 artanh((y - 1) / (y + 1))として計算する。
+# Panics
+x <= 0の場合
+*/
+pub(crate) fn ln_fixed(x: &Rational, w: usize) -> Integer {
+    if x.positive.sign() != Sign::Positive {
+        panic!("Argument of ln must be positive");
+    }
+    let m = x.positive.bit_length() as i64 - x.divider.bit_length() as i64;
+    let (numerator, denominator) = match m >= 0 {
+        true => (x.positive.clone(), &x.divider << m as usize),
+        false => (&x.positive << (-m) as usize, x.divider.clone()),
+    };
+    let work = w + GUARD_BITS;
+    let fraction = atanh_fixed(&(&numerator - &denominator), &(&numerator + &denominator), work) << 1;
+    let result = &(&ln2_fixed(work) * &Integer::from_i128(m as i128)) + &fraction;
+    return result >> GUARD_BITS;
+}
+
+/// |r| <= π/4の範囲でsin(r), cos(r)をテイラー展開で求める
+fn sin_cos_fixed(r: &Integer, w: usize) -> (Integer, Integer) {
+    let r_square = fixed_mul(r, r, w);
+    let mut sin = r.clone();
+    let mut term = r.clone();
+    let mut index: u128 = 1;
+    while term != Integer::zero() {
+        term = -(&fixed_mul(&term, &r_square, w) / &Integer::from_u128((2 * index) * (2 * index + 1)));
+        sin += term.clone();
+        index += 1;
+    }
+    let mut cos = Integer::one() << w;
+    let mut term = cos.clone();
+    let mut index: u128 = 1;
+    while term != Integer::zero() {
+        term = -(&fixed_mul(&term, &r_square, w) / &Integer::from_u128((2 * index - 1) * (2 * index)));
+        cos += term.clone();
+        index += 1;
+    }
+    return (sin, cos);
+}
+
+/**引数をπ/2の剰余に還元する
+
+x = k(π/2) + r (|r| <= π/4)となるkの下位2bitとrを返す。
+xが巨大な場合でもrの精度が落ちないように、xの大きさに応じてπの精度を上げて計算する。
+*/
+fn reduce_half_pi(x: &Rational, w: usize) -> (u8, Integer) {
+    let work = w + magnitude_bits(x) + GUARD_BITS;
+    let half_pi = pi_fixed(work) >> 1;
+    let x_fixed = rational_to_fixed(x, work);
+    //最も近い整数kを求める
+    let k = floor_div(&(&(&x_fixed << 1) + &half_pi), &(&half_pi << 1));
+    let r = &x_fixed - &(&k * &half_pi);
+    let quadrant = k.is_odd() as u8 + 2 * (&k >> 1).is_odd() as u8;
+    return (quadrant, r >> (work - w));
+}
+
+/// 象限を考慮してsin(x), cos(x)を求める
+pub(crate) fn sin_cos_rational_fixed(x: &Rational, w: usize) -> (Integer, Integer) {
+    let (quadrant, r) = reduce_half_pi(x, w);
+    let (sin, cos) = sin_cos_fixed(&r, w);
+    return match quadrant {
+        0 => (sin, cos),
+        1 => (cos, -sin),
+        2 => (-sin, -cos),
+        _ => (-cos, sin),
+    };
+}
+
+/// |t| <= 1の範囲でarctan(t)を求める
+fn atan_unit_fixed(t: &Integer, w: usize) -> Integer {
+    //arctan(t) = 2 arctan(t / (1 + sqrt(1 + t^2)))で|t| <= 1/16まで引数を小さくする
+    let one = Integer::one() << w;
+    let limit = Integer::one() << (w - 4);
+    let mut t = t.clone();
+    let mut halving = 0;
+    while t.abs() > limit {
+        let root = (&(&one << w) + &(&t * &t)).sqrt();
+        t = fixed_div(&t, &(&one + &root), w);
+        halving += 1;
+    }
+    let t_square = fixed_mul(&t, &t, w);
+    let mut power = t.clone();
+    let mut result = Integer::zero();
+    let mut index: u128 = 0;
+    while power != Integer::zero() {
+        result += &power / &Integer::from_u128(2 * index + 1);
+        power = -fixed_mul(&power, &t_square, w);
+        index += 1;
+    }
+    return result << halving;
+}
+
+/// 固定小数点数で与えられた座標(x, y)の偏角を求める
+fn atan2_fixed(y: &Integer, x: &Integer, w: usize) -> Integer {
+    let work = w + GUARD_BITS;
+    let y = y << GUARD_BITS;
+    let x = x << GUARD_BITS;
+    let pi = pi_fixed(work);
+    if x == Integer::zero() {
+        return match y.sign() {
+            Sign::Positive => pi >> (GUARD_BITS + 1),
+            Sign::Negative => -(pi >> (GUARD_BITS + 1)),
+            Sign::Zero => Integer::zero(),
+        };
+    }
+    let angle = match y.abs() <= x.abs() {
+        true => atan_unit_fixed(&fixed_div(&y, &x, work), work),
+        false => {
+            //|y/x| > 1の場合はarctan(t) = ±π/2 - arctan(1/t)を使う
+            let inverse = atan_unit_fixed(&fixed_div(&x, &y, work), work);
+            match y.sign() == x.sign() {
+                true => &(&pi >> 1) - &inverse,
+                false => -&(&(&pi >> 1) + &inverse),
+            }
+        }
+    };
+    let angle = match (x.sign(), y.sign()) {
+        (Sign::Negative, Sign::Negative) => &angle - &pi,
+        (Sign::Negative, _) => &angle + &pi,
+        _ => angle,
+    };
+    return angle >> GUARD_BITS;
+}
+
+/// 有理数tに対するarctan(t)を求める
+fn atan_rational_fixed(t: &Rational, w: usize) -> Integer {
+    let work = w + GUARD_BITS;
+    if t.positive.abs() <= t.divider {
+        return atan_unit_fixed(&rational_to_fixed(t, work), work) >> GUARD_BITS;
+    }
+    let inverse = atan_unit_fixed(&rational_to_fixed(&Rational::new(&t.divider, &t.positive), work), work);
+    let half_pi = pi_fixed(work) >> 1;
+    let result = match t.positive.sign() {
+        Sign::Negative => -&(&half_pi + &inverse),
+        _ => &half_pi - &inverse,
+    };
+    return result >> GUARD_BITS;
+}
+
+/// sqrt(1 - x^2)を固定小数点数で求める
+/// # Panics
+/// |x| > 1の場合
+fn complement_sqrt_fixed(x: &Rational, w: usize, name: &str) -> Integer {
+    let one = Rational::from(&Integer::one());
+    let remain = &one - &(x * x);
+    if remain.positive.sign() == Sign::Negative {
+        panic!("Argument of {} must be in [-1, 1]", name);
+    }
+    return rational_to_fixed(&remain, 2 * w).sqrt();
+}
+
+/**指数関数 exp(x)
+# Arguments
+* x - 引数
+* prec - 小数点以下の10進桁数
+# Returns
+exp(x)を小数点以下prec桁に丸めた値
+*/
+pub fn exp(x: &Rational, prec: usize) -> Rational {
+    let w = precision_bits(prec) + GUARD_BITS;
+    return fixed_to_rational(&exp_fixed(x, w), w, prec);
+}
+
+/**自然対数 log(x)
+# Arguments
+* x - 引数
+* prec - 小数点以下の10進桁数
+# Returns
+log(x)を小数点以下prec桁に丸めた値
+# Panics
+x <= 0の場合
+*/
+pub fn ln(x: &Rational, prec: usize) -> Rational {
+    let w = precision_bits(prec) + GUARD_BITS;
+    return fixed_to_rational(&ln_fixed(x, w), w, prec);
+}
+
+/**平方根 sqrt(x)
+# Arguments
+* x - 引数
+* prec - 小数点以下の10進桁数
+# Returns
+sqrt(x)を小数点以下prec桁に丸めた値
+# Panics
+x < 0の場合
+*/
+pub fn sqrt(x: &Rational, prec: usize) -> Rational {
+    if x.positive.sign() == Sign::Negative {
+        panic!("Argument of sqrt must not be negative");
+    }
+    let w = precision_bits(prec) + GUARD_BITS;
+    return fixed_to_rational(&rational_to_fixed(x, 2 * w).sqrt(), w, prec);
+}
+
+/**正弦関数 sin(x)
+# Arguments
+* x - 引数(ラジアン)
+* prec - 小数点以下の10進桁数
+# Returns
+sin(x)を小数点以下prec桁に丸めた値
+*/
+pub fn sin(x: &Rational, prec: usize) -> Rational {
+    let w = precision_bits(prec) + GUARD_BITS;
+    return fixed_to_rational(&sin_cos_rational_fixed(x, w).0, w, prec);
+}
+
+/**余弦関数 cos(x)
+# Arguments
+* x - 引数(ラジアン)
+* prec - 小数点以下の10進桁数
+# Returns
+cos(x)を小数点以下prec桁に丸めた値
+*/
+pub fn cos(x: &Rational, prec: usize) -> Rational {
+    let w = precision_bits(prec) + GUARD_BITS;
+    return fixed_to_rational(&sin_cos_rational_fixed(x, w).1, w, prec);
+}
+
+/**正接関数 tan(x)
+
+cos(x)が0に近い場合は、その分だけ内部精度を上げて再計算します。
+# Arguments
+* x - 引数(ラジアン)
+* prec - 小数点以下の10進桁数
+# Returns
+tan(x)を小数点以下prec桁に丸めた値
+*/
+pub fn tan(x: &Rational, prec: usize) -> Rational {
+    let target = precision_bits(prec) + GUARD_BITS;
+    //|cos(x)| >= 1/2なら最初の精度で足りる
+    let mut w = target + GUARD_BITS;
+    loop {
+        let (sin, cos) = sin_cos_rational_fixed(x, w);
+        //cosの先頭に並ぶ0の数だけ除算で誤差が拡大する
+        let leading_zeros = w.saturating_sub(cos.bit_length() as usize);
+        if w >= target + 2 * leading_zeros + GUARD_BITS {
+            let result = fixed_div(&sin, &cos, w) >> (w - target);
+            return fixed_to_rational(&result, target, prec);
+        }
+        w = target + 2 * leading_zeros + 2 * GUARD_BITS;
+    }
+}
+
+/**逆正接関数 arctan(x)
+# Arguments
+* x - 引数
+* prec - 小数点以下の10進桁数
+# Returns
+arctan(x)を小数点以下prec桁に丸めた値(範囲は[-π/2, π/2])
+*/
+pub fn atan(x: &Rational, prec: usize) -> Rational {
+    let w = precision_bits(prec) + GUARD_BITS;
+    return fixed_to_rational(&atan_rational_fixed(x, w), w, prec);
+}
+
+/**2引数の逆正接関数 arctan(y/x)
+
+点(x, y)の偏角を求めます。atan2(0, 0)は0を返します。
+# Arguments
+* y - y座標
+* x - x座標
+* prec - 小数点以下の10進桁数
+# Returns
+偏角を小数点以下prec桁に丸めた値(範囲は[-π, π])
+*/
+pub fn atan2(y: &Rational, x: &Rational, prec: usize) -> Rational {
+    let w = precision_bits(prec) + GUARD_BITS;
+    let result = match (x.positive.sign(), y.positive.sign()) {
+        (Sign::Zero, _) => atan2_fixed(&y.positive, &Integer::zero(), w),
+        (Sign::Positive, _) => atan_rational_fixed(&(y / x), w),
+        (Sign::Negative, sign) => {
+            let pi = pi_fixed(w);
+            let angle = atan_rational_fixed(&(y / x), w);
+            match sign {
+                Sign::Negative => &angle - &pi,
+                _ => &angle + &pi,
+            }
+        }
+    };
+    return fixed_to_rational(&result, w, prec);
+}
+
+/**逆正弦関数 arcsin(x)
+# Arguments
+* x - 引数
+* prec - 小数点以下の10進桁数
+# Returns
+arcsin(x)を小数点以下prec桁に丸めた値(範囲は[-π/2, π/2])
+# Panics
+|x| > 1の場合
+*/
+pub fn asin(x: &Rational, prec: usize) -> Rational {
+    let w = precision_bits(prec) + GUARD_BITS;
+    let cos = complement_sqrt_fixed(x, w, "asin");
+    return fixed_to_rational(&atan2_fixed(&rational_to_fixed(x, w), &cos, w), w, prec);
+}
+
+/**逆余弦関数 arccos(x)
+# Arguments
+* x - 引数
+* prec - 小数点以下の10進桁数
+# Returns
+arccos(x)を小数点以下prec桁に丸めた値(範囲は[0, π])
+# Panics
+|x| > 1の場合
+*/
+pub fn acos(x: &Rational, prec: usize) -> Rational {
+    let w = precision_bits(prec) + GUARD_BITS;
+    let sin = complement_sqrt_fixed(x, w, "acos");
+    return fixed_to_rational(&atan2_fixed(&sin, &rational_to_fixed(x, w), w), w, prec);
+}
+
+/// exp(x)とexp(-x)を同時に求める
+fn exp_pair_fixed(x: &Rational, w: usize) -> (Integer, Integer) {
+    let negative = Rational::new(&-&x.positive, &x.divider);
+    return (exp_fixed(x, w), exp_fixed(&negative, w));
+}
+
+/**双曲線正弦関数 sinh(x)
+# Arguments
+* x - 引数
+* prec - 小数点以下の10進桁数
+# Returns
+sinh(x)を小数点以下prec桁に丸めた値
+*/
+pub fn sinh(x: &Rational, prec: usize) -> Rational {
+    let w = precision_bits(prec) + GUARD_BITS;
+    let (positive, negative) = exp_pair_fixed(x, w);
+    return fixed_to_rational(&(&positive - &negative), w + 1, prec);
+}
+
+/**双曲線余弦関数 cosh(x)
+# Arguments
+* x - 引数
+* prec - 小数点以下の10進桁数
+# Returns
+cosh(x)を小数点以下prec桁に丸めた値
+*/
+pub fn cosh(x: &Rational, prec: usize) -> Rational {
+    let w = precision_bits(prec) + GUARD_BITS;
+    let (positive, negative) = exp_pair_fixed(x, w);
+    return fixed_to_rational(&(&positive + &negative), w + 1, prec);
+}
+
+/**双曲線正接関数 tanh(x)
+
+xが大きい場合でも桁あふれしないよう、exp(-2|x|)から計算します。
+# Arguments
+* x - 引数
+* prec - 小数点以下の10進桁数
+# Returns
+tanh(x)を小数点以下prec桁に丸めた値
+*/
+pub fn tanh(x: &Rational, prec: usize) -> Rational {
+    let w = precision_bits(prec) + GUARD_BITS;
+    //tanh(|x|) = (1 - exp(-2|x|)) / (1 + exp(-2|x|))
+    let double_abs = Rational::new(&(&x.positive.abs() << 1), &x.divider);
+    let decay = exp_fixed(&Rational::new(&-&double_abs.positive, &double_abs.divider), w);
+    let one = Integer::one() << w;
+    let result = fixed_div(&(&one - &decay), &(&one + &decay), w);
+    let result = match x.positive.sign() {
+        Sign::Negative => -result,
+        _ => result,
+    };
+    return fixed_to_rational(&result, w, prec);
+}
+
+#[cfg(test)]
+mod functions_test {
+    use super::*;
+
+    /// 期待値(10進数表記)との差が10^-prec以下であることを確認する
+    fn assert_close(actual: &Rational, expected: &str, prec: usize) {
+        let expected: Rational = expected.parse().unwrap();
+        let tolerance = Rational::new(
+            &Integer::one(),
+            &Integer::from_u128(10).pow(Integer::from_u128(prec as u128)),
+        );
+        let diff = actual - &expected;
+        let diff = Rational::new(&diff.positive.abs(), &diff.divider);
+        assert!(diff <= tolerance, "actual {} expected {}", actual, expected);
+    }
+
+    fn rational(s: &str) -> Rational {
+        return s.parse().unwrap();
+    }
+
+    const PI_100: &str = "3.1415926535897932384626433832795028841971693993751058209749445923078164062862089986280348253421170679";
+
+    #[test]
+    fn test_exp_ln_sqrt() {
+        assert_close(&exp(&rational("1"), 50), "2.718281828459045235360287471352662497757247093699959574967", 50);
+        assert_close(&exp(&rational("-5/2"), 50), "0.0820849986238987951695286744671598078378041210154366488457584", 50);
+        assert_close(&ln(&rational("10"), 50), "2.30258509299404568401799145468436420760110148862877297603333", 50);
+        assert_close(&ln(&rational("1/3"), 50), "-1.09861228866810969139524523692252570464749055782274945173", 50);
+        assert_close(&sqrt(&rational("2"), 50), "1.414213562373095048801688724209698078569671875376948073177", 50);
+        assert_eq!(sqrt(&rational("9/4"), 10), rational("3/2"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ln_domain() {
+        ln(&rational("0"), 10);
+    }
+
+    #[test]
+    fn test_sin_cos() {
+        assert_close(&sin(&rational("1"), 50), "0.841470984807896506652502321630298999622563060798371065672752", 50);
+        assert_close(&cos(&rational("1"), 50), "0.540302305868139717400936607442976603732310420617922227670097", 50);
+        assert_close(&sin(&rational("-7/2"), 40), "0.3507832276896198481203688000436355850850", 40);
+        assert_eq!(sin(&Rational::from(&Integer::zero()), 20), Rational::from(&Integer::zero()));
+    }
+
+    #[test]
+    fn test_huge_argument() {
+        //10^100 rad
+        let x = Rational::from(&Integer::from_u128(10).pow(Integer::from_u128(100)));
+        assert_close(&sin(&x, 40), "-0.3723761236612766882620866955531642957197", 40);
+        assert_close(&cos(&x, 40), "-0.9280819050746553434561946437769559281832", 40);
+    }
+
+    #[test]
+    fn test_tan() {
+        assert_close(&tan(&rational("1"), 50), "1.55740772465490223050697480745836017308725077238152003838395", 50);
+        //π/2の近傍
+        assert_close(&tan(&rational("355/226"), 30), "-7497258.185325587112905071831891248663", 30);
+        //cosの先頭に0が並ぶ場合と並ばない場合
+        assert_close(&tan(&rational("3/2"), 50), "14.1014199471717193876460836519877564456595435772358618661", 50);
+        assert_close(&tan(&rational("-1/3"), 50), "-0.34625354951057549103854356560974077459570391618980021797", 50);
+    }
+
+    #[test]
+    fn test_inverse() {
+        assert_close(&(&atan(&rational("1"), 100) * &rational("4")), PI_100, 99);
+        assert_close(&atan(&rational("-3"), 40), "-1.2490457723982544258299170772810901230778", 40);
+        assert_close(&atan2(&rational("1"), &rational("-1"), 40), "2.3561944901923449288469825374596271631479", 40);
+        assert_close(&atan2(&rational("-1"), &rational("0"), 40), "-1.5707963267948966192313216916397514420986", 40);
+        assert_close(&asin(&rational("1/2"), 40), "0.5235987755982988730771072305465838140329", 40);
+        assert_close(&acos(&rational("-1"), 40), PI_100, 40);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_asin_domain() {
+        asin(&rational("3/2"), 10);
+    }
+
+    #[test]
+    fn test_hyperbolic() {
+        assert_close(&sinh(&rational("1"), 50), "1.17520119364380145688238185059560081515571798133409587022957", 50);
+        assert_close(&cosh(&rational("-1"), 50), "1.5430806348152437784779056207570616826015291123658637047374", 50);
+        assert_close(&tanh(&rational("1/2"), 50), "0.462117157260009758502318483643672548730289280330113038552732", 50);
+        assert_close(&tanh(&rational("-1000"), 50), "-1", 50);
+        assert_close(&sinh(&rational("100"), 10), "13440585709080677242063127757900067936805559.3868709612", 10);
+    }
+}
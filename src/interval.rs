@@ -0,0 +1,468 @@
+/**区間演算を提供するモジュールです
+
+区間の端点は`Rational`で表すため、四則演算は丸め誤差なしに厳密に計算されます。
+初等関数は[`crate::functions`]の近似値(誤差`10^-prec`以下)をその誤差分だけ外側に広げることで、
+真の値域を必ず含む区間(精度保証付きの包含)を返します。
+*/
+use std::cmp::{max, min};
+use std::fmt;
+use std::ops::*;
+
+use crate::constants;
+use crate::functions;
+use crate::math_traits::*;
+use crate::num::{Integer, Rational, Sign};
+
+/// rfmライブラリにおける閉区間[lower, upper]の表現です。
+/// Closed interval expression in rfm library.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Interval {
+    ///下端
+    lower: Rational,
+    ///上端
+    upper: Rational,
+}
+
+/// 精度prec桁での誤差幅10^-precを求める
+fn tolerance(prec: usize) -> Rational {
+    return Rational::new(
+        &Integer::one(),
+        &Integer::from_u128(10).pow(Integer::from_u128(prec as u128)),
+    );
+}
+
+/// 有理数の符号
+fn sign_of(x: &Rational) -> Sign {
+    return x.positive.sign();
+}
+
+impl Interval {
+    /**
+    新たな区間を作成する
+    # Arguments
+    * lower - 下端
+    * upper - 上端
+    # Panics
+    lower > upperの場合
+     */
+    pub fn new(lower: &Rational, upper: &Rational) -> Interval {
+        if lower > upper {
+            panic!("lower bound must not exceed upper bound");
+        }
+        return Interval {
+            lower: lower.clone(),
+            upper: upper.clone(),
+        };
+    }
+
+    /// 1点のみからなる区間[x, x]を作成する
+    pub fn point(x: &Rational) -> Interval {
+        return Interval::new(x, x);
+    }
+
+    /// 下端を取得する
+    pub fn lower(&self) -> &Rational {
+        return &self.lower;
+    }
+
+    /// 上端を取得する
+    pub fn upper(&self) -> &Rational {
+        return &self.upper;
+    }
+
+    /// 区間の幅 upper - lower
+    pub fn width(&self) -> Rational {
+        return &self.upper - &self.lower;
+    }
+
+    /// 区間の中点
+    pub fn midpoint(&self) -> Rational {
+        return &(&self.lower + &self.upper) / &Rational::from(&Integer::from_u128(2));
+    }
+
+    /// 値xが区間に含まれるかを判定する
+    pub fn contains(&self, x: &Rational) -> bool {
+        return &self.lower <= x && x <= &self.upper;
+    }
+
+    /// 区間otherが区間に含まれるかを判定する
+    pub fn contains_interval(&self, other: &Interval) -> bool {
+        return self.lower <= other.lower && other.upper <= self.upper;
+    }
+
+    /// 2つの区間の共通部分を求める(共通部分がなければNone)
+    pub fn intersect(&self, other: &Interval) -> Option<Interval> {
+        let lower = max(&self.lower, &other.lower);
+        let upper = min(&self.upper, &other.upper);
+        if lower > upper {
+            return None;
+        }
+        return Some(Interval::new(lower, upper));
+    }
+
+    /// 2つの区間を含む最小の区間を求める
+    pub fn hull(&self, other: &Interval) -> Interval {
+        return Interval::new(min(&self.lower, &other.lower), max(&self.upper, &other.upper));
+    }
+
+    /// 区間の各点の絶対値が取りうる範囲を求める
+    pub fn abs(&self) -> Interval {
+        if sign_of(&self.lower) != Sign::Negative {
+            return self.clone();
+        }
+        if sign_of(&self.upper) != Sign::Positive {
+            return -self;
+        }
+        let zero = Rational::from(&Integer::zero());
+        return Interval::new(&zero, max(&-&self.lower, &self.upper));
+    }
+
+    /// 累乗 x^exp の値域を求める
+    pub fn pow(&self, exp: u64) -> Interval {
+        let exponent = Integer::from_u64(exp);
+        let power = |x: &Rational| Rational::new(&x.positive.pow(exponent.clone()), &x.divider.pow(exponent.clone()));
+        if exp % 2 == 1 {
+            return Interval::new(&power(&self.lower), &power(&self.upper));
+        }
+        let magnitude = self.abs();
+        return Interval::new(&power(&magnitude.lower), &power(&magnitude.upper));
+    }
+
+    /// 単調増加関数の値域を、近似誤差の分だけ広げて求める
+    fn increasing(&self, prec: usize, function: fn(&Rational, usize) -> Rational) -> Interval {
+        let error = tolerance(prec);
+        return Interval::new(
+            &(&function(&self.lower, prec) - &error),
+            &(&function(&self.upper, prec) + &error),
+        );
+    }
+
+    /// 単調減少関数の値域を、近似誤差の分だけ広げて求める
+    fn decreasing(&self, prec: usize, function: fn(&Rational, usize) -> Rational) -> Interval {
+        let error = tolerance(prec);
+        return Interval::new(
+            &(&function(&self.upper, prec) - &error),
+            &(&function(&self.lower, prec) + &error),
+        );
+    }
+
+    /// 極値をとる点が(k + offset)π (kは整数)にある周期関数の値域を求める
+    ///
+    /// 区間内に極値をとる点が含まれる可能性がある場合は、その極値(±1)も値域に含める。
+    /// 極値をとる点は円周率の包含区間から求めるため、判定は常に安全側となる。
+    fn periodic(&self, prec: usize, offset: &Rational, function: fn(&Rational, usize) -> Rational) -> Interval {
+        let error = tolerance(prec);
+        let one = Rational::from(&Integer::one());
+        let full = Interval::new(&-&one, &one);
+        //端点の大きさの桁数だけ円周率の精度を上げる
+        let magnitude = max(self.lower.positive.abs(), self.upper.positive.abs());
+        let pi_prec = prec + magnitude.to_string().len();
+        let pi = constants::pi(pi_prec);
+        let pi_error = tolerance(pi_prec);
+        let pi_range = Interval::new(&(&pi - &pi_error), &(&pi + &pi_error));
+        if self.width() >= &pi_range.lower * &Rational::from(&Integer::from_u128(2)) {
+            return full;
+        }
+
+        let lower_value = function(&self.lower, prec);
+        let upper_value = function(&self.upper, prec);
+        let mut result = Interval::new(
+            &(&min(&lower_value, &upper_value).clone() - &error),
+            &(&max(&lower_value, &upper_value).clone() + &error),
+        );
+        //区間の近くにある極値の候補kを列挙する
+        let center = &(&self.midpoint() / &pi) - offset;
+        let center = &center.positive / &center.divider;
+        for shift in -2..=2 {
+            let k = &center + &Integer::from_i128(shift);
+            let factor = &Rational::from(&k) + offset;
+            let point = &pi_range * &Interval::point(&factor);
+            if point.intersect(self).is_some() {
+                let extremum = match k.is_even() {
+                    true => one.clone(),
+                    false => -&one,
+                };
+                result = result.hull(&Interval::point(&extremum));
+            }
+        }
+        return result.intersect(&full).unwrap_or(full);
+    }
+
+    /// 平方根の値域
+    /// # Panics
+    /// 区間が負の数を含む場合
+    pub fn sqrt(&self, prec: usize) -> Interval {
+        let result = self.increasing(prec, functions::sqrt);
+        let zero = Rational::from(&Integer::zero());
+        return Interval::new(max(&result.lower, &zero), &result.upper);
+    }
+
+    /// 指数関数の値域
+    pub fn exp(&self, prec: usize) -> Interval {
+        let result = self.increasing(prec, functions::exp);
+        let zero = Rational::from(&Integer::zero());
+        return Interval::new(max(&result.lower, &zero), &result.upper);
+    }
+
+    /// 自然対数の値域
+    /// # Panics
+    /// 区間が0以下の数を含む場合
+    pub fn ln(&self, prec: usize) -> Interval {
+        return self.increasing(prec, functions::ln);
+    }
+
+    /// 正弦関数の値域
+    pub fn sin(&self, prec: usize) -> Interval {
+        //sinはπ/2 + kπで極値(kが偶数のとき1)をとる
+        let offset = Rational::new(&Integer::one(), &Integer::from_u128(2));
+        return self.periodic(prec, &offset, functions::sin);
+    }
+
+    /// 余弦関数の値域
+    pub fn cos(&self, prec: usize) -> Interval {
+        //cosはkπで極値(kが偶数のとき1)をとる
+        let offset = Rational::from(&Integer::zero());
+        return self.periodic(prec, &offset, functions::cos);
+    }
+
+    /// 正接関数の値域
+    /// # Panics
+    /// 区間が極(π/2 + kπ)を含む可能性がある場合
+    pub fn tan(&self, prec: usize) -> Interval {
+        let cos = self.cos(prec + 1);
+        let sign = sign_of(&cos.lower);
+        if sign == Sign::Zero || sign != sign_of(&cos.upper) {
+            panic!("Interval may contain a pole of tan");
+        }
+        return self.increasing(prec, functions::tan);
+    }
+
+    /// 逆正接関数の値域
+    pub fn atan(&self, prec: usize) -> Interval {
+        return self.increasing(prec, functions::atan);
+    }
+
+    /// 逆正弦関数の値域
+    /// # Panics
+    /// 区間が[-1, 1]に含まれない場合
+    pub fn asin(&self, prec: usize) -> Interval {
+        return self.increasing(prec, functions::asin);
+    }
+
+    /// 逆余弦関数の値域
+    /// # Panics
+    /// 区間が[-1, 1]に含まれない場合
+    pub fn acos(&self, prec: usize) -> Interval {
+        return self.decreasing(prec, functions::acos);
+    }
+
+    /// 双曲線正弦関数の値域
+    pub fn sinh(&self, prec: usize) -> Interval {
+        return self.increasing(prec, functions::sinh);
+    }
+
+    /// 双曲線余弦関数の値域
+    pub fn cosh(&self, prec: usize) -> Interval {
+        let magnitude = self.abs();
+        let result = magnitude.increasing(prec, functions::cosh);
+        let one = Rational::from(&Integer::one());
+        return Interval::new(max(&result.lower, &one), &result.upper);
+    }
+
+    /// 双曲線正接関数の値域
+    pub fn tanh(&self, prec: usize) -> Interval {
+        return self.increasing(prec, functions::tanh);
+    }
+}
+
+impl Add for &Interval {
+    type Output = Interval;
+    fn add(self, rhs: Self) -> Self::Output {
+        return Interval::new(&(&self.lower + &rhs.lower), &(&self.upper + &rhs.upper));
+    }
+}
+
+impl Add for Interval {
+    type Output = Interval;
+    fn add(self, rhs: Self) -> Self::Output {
+        return &self + &rhs;
+    }
+}
+
+impl AddAssign for Interval {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = &*self + &rhs;
+    }
+}
+
+impl Neg for &Interval {
+    type Output = Interval;
+    fn neg(self) -> Self::Output {
+        return Interval::new(&-&self.upper, &-&self.lower);
+    }
+}
+
+impl Neg for Interval {
+    type Output = Interval;
+    fn neg(self) -> Self::Output {
+        return -&self;
+    }
+}
+
+impl Sub for &Interval {
+    type Output = Interval;
+    fn sub(self, rhs: Self) -> Self::Output {
+        return Interval::new(&(&self.lower - &rhs.upper), &(&self.upper - &rhs.lower));
+    }
+}
+
+impl Sub for Interval {
+    type Output = Interval;
+    fn sub(self, rhs: Self) -> Self::Output {
+        return &self - &rhs;
+    }
+}
+
+impl SubAssign for Interval {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = &*self - &rhs;
+    }
+}
+
+impl Mul for &Interval {
+    type Output = Interval;
+    fn mul(self, rhs: Self) -> Self::Output {
+        let products = [
+            &self.lower * &rhs.lower,
+            &self.lower * &rhs.upper,
+            &self.upper * &rhs.lower,
+            &self.upper * &rhs.upper,
+        ];
+        return Interval::new(products.iter().min().unwrap(), products.iter().max().unwrap());
+    }
+}
+
+impl Mul for Interval {
+    type Output = Interval;
+    fn mul(self, rhs: Self) -> Self::Output {
+        return &self * &rhs;
+    }
+}
+
+impl MulAssign for Interval {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = &*self * &rhs;
+    }
+}
+
+impl Div for &Interval {
+    type Output = Interval;
+    /// # Panics
+    /// 除数の区間が0を含む場合
+    fn div(self, rhs: Self) -> Self::Output {
+        let zero = Rational::from(&Integer::zero());
+        if rhs.contains(&zero) {
+            panic!("Divide by zero");
+        }
+        let one = Rational::from(&Integer::one());
+        let inverse = Interval::new(&(&one / &rhs.upper), &(&one / &rhs.lower));
+        return self * &inverse;
+    }
+}
+
+impl Div for Interval {
+    type Output = Interval;
+    fn div(self, rhs: Self) -> Self::Output {
+        return &self / &rhs;
+    }
+}
+
+impl DivAssign for Interval {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = &*self / &rhs;
+    }
+}
+
+impl fmt::Display for Interval {
+    /// `[下端, 上端]`の形式で出力する
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return f.pad(&format!("[{}, {}]", self.lower, self.upper));
+    }
+}
+
+#[cfg(test)]
+mod interval_test {
+    use super::Interval;
+    use crate::num::Rational;
+
+    fn rational(s: &str) -> Rational {
+        return s.parse().unwrap();
+    }
+
+    fn interval(lower: &str, upper: &str) -> Interval {
+        return Interval::new(&rational(lower), &rational(upper));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = interval("1", "2");
+        let b = interval("-3", "1/2");
+        assert_eq!(&a + &b, interval("-2", "5/2"));
+        assert_eq!(&a - &b, interval("1/2", "5"));
+        assert_eq!(&a * &b, interval("-6", "1"));
+        assert_eq!(&b / &a, interval("-3", "1/2"));
+        assert_eq!(-&b, interval("-1/2", "3"));
+        assert_eq!(b.pow(2), interval("0", "9"));
+        assert_eq!(b.pow(3), interval("-27", "1/8"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_div_zero() {
+        let _ = interval("1", "2") / interval("-1", "1");
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = interval("0", "2");
+        let b = interval("1", "3");
+        assert_eq!(a.intersect(&b), Some(interval("1", "2")));
+        assert_eq!(a.intersect(&interval("5/2", "3")), None);
+        assert_eq!(a.hull(&b), interval("0", "3"));
+        assert_eq!(b.width(), rational("2"));
+        assert!(a.contains(&rational("3/2")));
+        assert!(!a.contains(&rational("-1/2")));
+        assert_eq!(a.to_string(), "[0, 2]");
+    }
+
+    #[test]
+    fn test_functions_enclose() {
+        let x = interval("1", "2");
+        let sin = x.sin(30);
+        //π/2が区間内にあるので最大値1を含む
+        assert_eq!(sin.upper(), &rational("1"));
+        assert!(sin.contains(&rational("0.841470984807896506652502321630")));
+        assert!(!sin.contains(&rational("0.84147")));
+
+        let cos = interval("3", "4").cos(30);
+        assert_eq!(cos.lower(), &rational("-1"));
+
+        let exp = interval("1", "1").exp(40);
+        assert!(exp.contains(&rational("2.71828182845904523536028747135266249775724709")));
+        assert!(exp.width() <= rational("1/1000000000000000000000000000000000000000") * rational("2"));
+
+        let cosh = interval("-1", "2").cosh(20);
+        assert_eq!(cosh.lower(), &rational("1"));
+        assert!(cosh.contains(&rational("3.762195691083631459562213477")));
+
+        let acos = interval("-1/2", "1/2").acos(30);
+        assert!(acos.contains(&rational("1.0471975512")));
+        assert!(acos.contains(&rational("2.0943951023")));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_tan_pole() {
+        interval("1", "2").tan(20);
+    }
+}
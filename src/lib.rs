@@ -1,9 +1,29 @@
 //!Rust用任意精度演算ライブラリ
 //! 本ライブラリはRust向けに任意精度演算を提供するライブラリです。
 //! 
+#![allow(clippy::needless_return)]
+#![cfg_attr(test, allow(clippy::useless_vec))]
 
 ///値の管理用モジュール
 pub mod num;
 pub mod math_traits;
 pub mod algorithm;
-mod arithmetic_util;
\ No newline at end of file
+pub mod functions;
+pub mod constants;
+pub mod complex;
+pub mod interval;
+pub mod continued_fraction;
+pub mod modint;
+pub mod polynomial;
+pub mod root_isolation;
+pub mod parallel;
+mod arithmetic_util;
+mod digits;
+mod fixed_point;
+mod operators;
+#[cfg(test)]
+mod test_util;
+#[cfg(feature = "num-traits")]
+mod num_traits_compat;
+#[cfg(feature = "serde")]
+mod serde_compat;
\ No newline at end of file
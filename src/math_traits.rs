@@ -1,5 +1,4 @@
-/// 数値演算に関するtraitの集合体
-/// 
+//! 数値演算に関するtraitの集合体
 
 use std::ops::*;
 
@@ -34,14 +33,22 @@ pub trait FromPrimitiveNumber:Sized{
     fn from_u128(val:u128) -> Self;
 }
 
-///乗除算における単位元の定義
-pub trait Zero:Add + Sub + Mul + Div + Rem + Sized{
+///加減算における単位元の定義
+pub trait Zero:Add + Sized{
     fn zero()->Self;
+    ///0であるかを判定する
+    fn is_zero(&self) -> bool where Self:PartialEq{
+        return *self == Self::zero();
+    }
 }
 
-///加減算における単位元の定義
-pub trait One:Add + Sub + Mul + Div + Rem + Sized{
+///乗除算における単位元の定義
+pub trait One:Mul + Sized{
     fn one()->Self;
+    ///1であるかを判定する
+    fn is_one(&self) -> bool where Self:PartialEq{
+        return *self == Self::one();
+    }
 }
 
 ///偶奇判定
@@ -60,3 +67,209 @@ pub trait Pow:EvenOdd + FromPrimitiveNumber + Div + Sized{
 pub trait DivRem:Div + Rem + Sized{
     fn div_rem(&self,rhs:&Self) -> (Self,Self);
 }
+
+///環(加算・減算・乗算について閉じており、0と1を持つ数)
+pub trait Ring:Zero + One + Clone + PartialEq + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>{
+}
+
+///ユークリッド整域(余りつき除算が可能な環)
+pub trait EuclideanDomain:Ring + DivRem + Div<Output = Self> + Rem<Output = Self>{
+    ///最大公約数を求める
+    fn gcd(&self,other:&Self) -> Self{
+        let mut a = self.clone();
+        let mut b = other.clone();
+        while !b.is_zero() {
+            let r = a.div_rem(&b).1;
+            a = b;
+            b = r;
+        }
+        return a;
+    }
+
+    ///最小公倍数を求める
+    ///符号を持つ型では、gcdと同様に0以上の値を返すよう実装する
+    fn lcm(&self,other:&Self) -> Self{
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+        return self.clone() / self.gcd(other) * other.clone();
+    }
+}
+
+///体(0以外の数で除算が可能な環)
+pub trait Field:Ring + Div<Output = Self>{
+    ///逆数を求める
+    fn recip(&self) -> Self{
+        return Self::one() / self.clone();
+    }
+}
+
+///符号付きの数
+pub trait Signed:Ring + Neg<Output = Self> + PartialOrd{
+    ///絶対値
+    fn abs(&self) -> Self{
+        if self.is_negative() {
+            return -self.clone();
+        }
+        return self.clone();
+    }
+    ///符号を-1, 0, 1で返す
+    fn signum(&self) -> Self{
+        if self.is_negative() {
+            return -Self::one();
+        }
+        if self.is_positive() {
+            return Self::one();
+        }
+        return Self::zero();
+    }
+    ///正の数であるか
+    fn is_positive(&self) -> bool{
+        return *self > Self::zero();
+    }
+    ///負の数であるか
+    fn is_negative(&self) -> bool{
+        return *self < Self::zero();
+    }
+}
+
+///最小値と最大値を持つ数
+pub trait Bounded{
+    fn min_value() -> Self;
+    fn max_value() -> Self;
+}
+
+///プリミティブ整数型へのトレイト実装
+macro_rules! impl_primitive_integer {
+    ($($t:ty),*) => {$(
+        impl Zero for $t {
+            fn zero() -> Self {
+                return 0;
+            }
+        }
+
+        impl One for $t {
+            fn one() -> Self {
+                return 1;
+            }
+        }
+
+        impl EvenOdd for $t {
+            fn is_even(&self) -> bool {
+                return *self % 2 == 0;
+            }
+            fn is_odd(&self) -> bool {
+                return *self % 2 != 0;
+            }
+        }
+
+        impl DivRem for $t {
+            fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+                return (*self / *rhs, *self % *rhs);
+            }
+        }
+
+        impl Bounded for $t {
+            fn min_value() -> Self {
+                return <$t>::MIN;
+            }
+            fn max_value() -> Self {
+                return <$t>::MAX;
+            }
+        }
+
+        impl Ring for $t {}
+    )*};
+}
+
+///符号付きプリミティブ整数型へのトレイト実装
+macro_rules! impl_signed_primitive_integer {
+    ($($t:ty),*) => {$(
+        impl_primitive_integer!($t);
+
+        impl EuclideanDomain for $t {
+            fn gcd(&self, other: &Self) -> Self {
+                let mut a = *self;
+                let mut b = *other;
+                while b != 0 {
+                    (a, b) = (b, a % b);
+                }
+                return a.abs();
+            }
+
+            fn lcm(&self, other: &Self) -> Self {
+                if *self == 0 || *other == 0 {
+                    return 0;
+                }
+                return (*self / EuclideanDomain::gcd(self, other) * *other).abs();
+            }
+        }
+
+        impl Signed for $t {}
+    )*};
+}
+
+///符号なしプリミティブ整数型へのトレイト実装
+macro_rules! impl_unsigned_primitive_integer {
+    ($($t:ty),*) => {$(
+        impl_primitive_integer!($t);
+
+        impl EuclideanDomain for $t {}
+    )*};
+}
+
+impl_signed_primitive_integer!(i8, i16, i32, i64, i128, isize);
+impl_unsigned_primitive_integer!(u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod math_traits_test {
+    use super::*;
+    use crate::num::{Integer, Rational};
+
+    ///環上で定義された汎用関数の例 x^2 + x + 1
+    fn polynomial<T: Ring>(x: &T) -> T {
+        return x.clone() * x.clone() + x.clone() + T::one();
+    }
+
+    #[test]
+    fn ring_test() {
+        assert_eq!(polynomial(&3i64), 13);
+        assert_eq!(polynomial(&3u8), 13);
+        assert_eq!(polynomial(&Integer::from_i128(-4)), Integer::from_i128(13));
+        assert_eq!(
+            polynomial(&"1/2".parse::<Rational>().unwrap()),
+            "7/4".parse::<Rational>().unwrap()
+        );
+        assert!(Integer::zero().is_zero());
+        assert!(Rational::one().is_one());
+    }
+
+    #[test]
+    fn euclidean_domain_test() {
+        assert_eq!(EuclideanDomain::gcd(&-12i32, &18), 6);
+        assert_eq!(12u64.lcm(&18), 36);
+        assert_eq!((-12i32).lcm(&18), 36);
+        assert_eq!(12i64.lcm(&-18), 36);
+        assert_eq!(0i32.lcm(&-18), 0);
+        let a = Integer::from_i128(-12);
+        let b = Integer::from_i128(18);
+        assert_eq!(EuclideanDomain::gcd(&a, &b), Integer::from_i128(6));
+        assert_eq!(a.lcm(&b), Integer::from_i128(36));
+        assert_eq!(b.lcm(&a), Integer::from_i128(36));
+        assert_eq!((-&a).lcm(&-&b), Integer::from_i128(36));
+        assert_eq!(a.lcm(&Integer::zero()), Integer::zero());
+    }
+
+    #[test]
+    fn field_signed_test() {
+        let x: Rational = "-3/4".parse().unwrap();
+        assert_eq!(x.recip(), "-4/3".parse().unwrap());
+        assert_eq!(Signed::abs(&x), "3/4".parse().unwrap());
+        assert_eq!(Signed::signum(&x), Rational::from_i128(-1));
+        assert!(x.is_negative());
+        assert_eq!(Signed::signum(&Integer::from_i128(5)), Integer::one());
+        assert_eq!(Signed::abs(&-7i16), 7);
+        assert_eq!(<u8 as Bounded>::max_value(), 255);
+        assert_eq!(<i8 as Bounded>::min_value(), -128);
+    }
+}
@@ -0,0 +1,532 @@
+/**剰余環 Z/nZ の元を扱うモジュールです
+
+法を実行時に決める`ModInt`と、法をコンパイル時に決める`ConstModInt`の2種類を提供します。
+`ModInt`は法を`Arc`で共有するため、同じ法の元を大量に作っても法の複製は発生しません。
+`ConstModInt`は法が`u64`に収まる場合に使え、演算は`u128`の範囲で完結します。
+法が素数の場合は0以外の全ての元が逆元を持ち、除算が可能です。
+*/
+use std::fmt;
+use std::ops::*;
+use std::sync::Arc;
+
+use crate::algorithm::{jacobi_symbol, mod_inverse, mod_pow, rem_euclid, sqrt_mod};
+use crate::math_traits::*;
+use crate::num::{Integer, Sign};
+
+/// 実行時に決める法
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Modulus(Arc<Integer>);
+
+impl Modulus {
+    /// 法を作成する
+    /// # Panics
+    /// 法が2未満の場合
+    pub fn new(modulus: &Integer) -> Modulus {
+        assert!(*modulus >= Integer::from_u32(2), "modulus must be at least 2");
+        return Modulus(Arc::new(modulus.clone()));
+    }
+
+    /// 法の値を取得する
+    pub fn value(&self) -> &Integer {
+        return &self.0;
+    }
+
+    /// 整数を剰余類に変換する
+    pub fn element(&self, value: &Integer) -> ModInt {
+        return ModInt {
+            residue: rem_euclid(value, &self.0),
+            modulus: self.clone(),
+        };
+    }
+}
+
+/// 実行時に決めた法による剰余類
+///
+/// 異なる法の元同士を演算するとパニックします。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModInt {
+    //常に0以上法未満
+    residue: Integer,
+    modulus: Modulus,
+}
+
+impl ModInt {
+    /// 整数と法から剰余類を作成する
+    /// # Panics
+    /// 法が2未満の場合
+    pub fn new(value: &Integer, modulus: &Integer) -> ModInt {
+        return Modulus::new(modulus).element(value);
+    }
+
+    /// 0以上法未満の代表元を取得する
+    pub fn residue(&self) -> &Integer {
+        return &self.residue;
+    }
+
+    /// 法を取得する
+    pub fn modulus(&self) -> &Modulus {
+        return &self.modulus;
+    }
+
+    fn with_residue(&self, residue: Integer) -> ModInt {
+        return ModInt {
+            residue,
+            modulus: self.modulus.clone(),
+        };
+    }
+
+    fn check_modulus(&self, rhs: &ModInt) {
+        assert!(
+            Arc::ptr_eq(&self.modulus.0, &rhs.modulus.0) || self.modulus == rhs.modulus,
+            "modulus mismatch"
+        );
+    }
+
+    /// 逆元を求める(存在しない場合はNone)
+    pub fn inverse(&self) -> Option<ModInt> {
+        return mod_inverse(&self.residue, self.modulus.value()).map(|inverse| self.with_residue(inverse));
+    }
+
+    /// 累乗を求める
+    /// 指数が負の場合は逆元の累乗となる。
+    /// # Panics
+    /// 指数が負で逆元が存在しない場合
+    pub fn pow(&self, exp: &Integer) -> ModInt {
+        if exp.sign() == Sign::Negative {
+            let inverse = self.inverse().expect("element is not invertible");
+            return inverse.pow(&-exp);
+        }
+        return self.with_residue(mod_pow(&self.residue, exp, self.modulus.value()));
+    }
+
+    /// ヤコビ記号 (residue/modulus) を求める(法が素数の場合はルジャンドル記号)
+    /// # Panics
+    /// 法が奇数でない場合
+    pub fn jacobi(&self) -> i8 {
+        return jacobi_symbol(&self.residue, self.modulus.value());
+    }
+
+    /// 平方根を求める(法が奇素数の場合のみ有効)
+    /// # Returns
+    /// 2つの平方根のうち代表元が小さい方。平方非剰余の場合、または法が素数でないため求められない場合はNone
+    pub fn sqrt(&self) -> Option<ModInt> {
+        return sqrt_mod(&self.residue, self.modulus.value()).map(|root| self.with_residue(root));
+    }
+}
+
+impl Add<&ModInt> for &ModInt {
+    type Output = ModInt;
+    fn add(self, rhs: &ModInt) -> ModInt {
+        self.check_modulus(rhs);
+        let sum = &self.residue + &rhs.residue;
+        if sum >= *self.modulus.value() {
+            return self.with_residue(&sum - self.modulus.value());
+        }
+        return self.with_residue(sum);
+    }
+}
+
+impl Sub<&ModInt> for &ModInt {
+    type Output = ModInt;
+    fn sub(self, rhs: &ModInt) -> ModInt {
+        self.check_modulus(rhs);
+        let difference = &self.residue - &rhs.residue;
+        if difference.sign() == Sign::Negative {
+            return self.with_residue(&difference + self.modulus.value());
+        }
+        return self.with_residue(difference);
+    }
+}
+
+impl Mul<&ModInt> for &ModInt {
+    type Output = ModInt;
+    fn mul(self, rhs: &ModInt) -> ModInt {
+        self.check_modulus(rhs);
+        return self.with_residue(&(&self.residue * &rhs.residue) % self.modulus.value());
+    }
+}
+
+impl Div<&ModInt> for &ModInt {
+    type Output = ModInt;
+    /// 逆元を掛ける
+    /// # Panics
+    /// 除数の逆元が存在しない場合
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: &ModInt) -> ModInt {
+        self.check_modulus(rhs);
+        return self * &rhs.inverse().expect("element is not invertible");
+    }
+}
+
+impl Neg for &ModInt {
+    type Output = ModInt;
+    fn neg(self) -> ModInt {
+        if self.residue.sign() == Sign::Zero {
+            return self.clone();
+        }
+        return self.with_residue(self.modulus.value() - &self.residue);
+    }
+}
+
+impl Neg for ModInt {
+    type Output = ModInt;
+    fn neg(self) -> ModInt {
+        return -&self;
+    }
+}
+
+impl fmt::Display for ModInt {
+    /// 代表元を出力する
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return fmt::Display::fmt(&self.residue, f);
+    }
+}
+
+/// 法がコンパイル時に決まる剰余類
+///
+/// 法Mは2以上でなければならず、違反するとコンパイルエラーになります。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConstModInt<const M: u64> {
+    //常に0以上M未満
+    value: u64,
+}
+
+impl<const M: u64> ConstModInt<M> {
+    const VALID_MODULUS: () = assert!(M >= 2, "modulus must be at least 2");
+
+    /// 整数から剰余類を作成する
+    pub fn new(value: u64) -> ConstModInt<M> {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID_MODULUS;
+        return ConstModInt { value: value % M };
+    }
+
+    /// 任意精度整数から剰余類を作成する
+    pub fn from_integer(value: &Integer) -> ConstModInt<M> {
+        let residue = rem_euclid(value, &Integer::from_u64(M));
+        return ConstModInt::new(residue.to_u64_digits().first().copied().unwrap_or(0));
+    }
+
+    /// 法を取得する
+    pub const fn modulus() -> u64 {
+        return M;
+    }
+
+    /// 0以上M未満の代表元を取得する
+    pub fn value(&self) -> u64 {
+        return self.value;
+    }
+
+    /// 累乗を求める
+    pub fn pow(&self, mut exp: u64) -> ConstModInt<M> {
+        let mut result = ConstModInt::new(1);
+        let mut base = *self;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+        return result;
+    }
+
+    /// 逆元を求める(存在しない場合はNone)
+    pub fn inverse(&self) -> Option<ConstModInt<M>> {
+        //拡張ユークリッドの互除法(係数はMの範囲に収まるためi128で足りる)
+        let (mut old_r, mut r) = (self.value as i128, M as i128);
+        let (mut old_x, mut x) = (1i128, 0i128);
+        while r != 0 {
+            let quotient = old_r / r;
+            (old_r, r) = (r, old_r - quotient * r);
+            (old_x, x) = (x, old_x - quotient * x);
+        }
+        if old_r != 1 {
+            return None;
+        }
+        return Some(ConstModInt::new(old_x.rem_euclid(M as i128) as u64));
+    }
+
+    /// ヤコビ記号 (value/M) を求める(Mが素数の場合はルジャンドル記号)
+    /// # Panics
+    /// Mが奇数でない場合
+    pub fn jacobi(&self) -> i8 {
+        return jacobi_symbol(&Integer::from_u64(self.value), &Integer::from_u64(M));
+    }
+
+    /// 平方根を求める(Mが奇素数の場合のみ有効)
+    /// # Returns
+    /// 2つの平方根のうち代表元が小さい方。平方非剰余の場合、またはMが素数でないため求められない場合はNone
+    pub fn sqrt(&self) -> Option<ConstModInt<M>> {
+        let root = sqrt_mod(&Integer::from_u64(self.value), &Integer::from_u64(M))?;
+        return Some(ConstModInt::from_integer(&root));
+    }
+}
+
+impl<const M: u64> Add for ConstModInt<M> {
+    type Output = ConstModInt<M>;
+    fn add(self, rhs: ConstModInt<M>) -> ConstModInt<M> {
+        let sum = self.value as u128 + rhs.value as u128;
+        return ConstModInt { value: (sum % M as u128) as u64 };
+    }
+}
+
+impl<const M: u64> Sub for ConstModInt<M> {
+    type Output = ConstModInt<M>;
+    fn sub(self, rhs: ConstModInt<M>) -> ConstModInt<M> {
+        if self.value >= rhs.value {
+            return ConstModInt { value: self.value - rhs.value };
+        }
+        return ConstModInt { value: M - (rhs.value - self.value) };
+    }
+}
+
+impl<const M: u64> Mul for ConstModInt<M> {
+    type Output = ConstModInt<M>;
+    fn mul(self, rhs: ConstModInt<M>) -> ConstModInt<M> {
+        let product = self.value as u128 * rhs.value as u128;
+        return ConstModInt { value: (product % M as u128) as u64 };
+    }
+}
+
+impl<const M: u64> Div for ConstModInt<M> {
+    type Output = ConstModInt<M>;
+    /// 逆元を掛ける
+    /// # Panics
+    /// 除数の逆元が存在しない場合
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: ConstModInt<M>) -> ConstModInt<M> {
+        return self * rhs.inverse().expect("element is not invertible");
+    }
+}
+
+impl<const M: u64> Neg for ConstModInt<M> {
+    type Output = ConstModInt<M>;
+    fn neg(self) -> ConstModInt<M> {
+        return ConstModInt::new(0) - self;
+    }
+}
+
+/// 参照と値の組み合わせ、および複合代入演算子を基本の演算から実装する
+///
+/// by_refは`&T op &T`、by_valueは`T op T`(Copy型)が実装済みであることを前提とします。
+macro_rules! forward_operators {
+    ([$($generics:tt)*] $type:ty, $kind:ident) => {
+        forward_operators!(@each [$($generics)*] $type, $kind, Add add AddAssign add_assign);
+        forward_operators!(@each [$($generics)*] $type, $kind, Sub sub SubAssign sub_assign);
+        forward_operators!(@each [$($generics)*] $type, $kind, Mul mul MulAssign mul_assign);
+        forward_operators!(@each [$($generics)*] $type, $kind, Div div DivAssign div_assign);
+    };
+    (@each [$($generics:tt)*] $type:ty, by_ref, $op:ident $method:ident $assign:ident $assign_method:ident) => {
+        impl<$($generics)*> $op for $type {
+            type Output = $type;
+            fn $method(self, rhs: $type) -> $type {
+                return (&self).$method(&rhs);
+            }
+        }
+        impl<$($generics)*> $op<&$type> for $type {
+            type Output = $type;
+            fn $method(self, rhs: &$type) -> $type {
+                return (&self).$method(rhs);
+            }
+        }
+        impl<$($generics)*> $op<$type> for &$type {
+            type Output = $type;
+            fn $method(self, rhs: $type) -> $type {
+                return self.$method(&rhs);
+            }
+        }
+        impl<$($generics)*> $assign<&$type> for $type {
+            fn $assign_method(&mut self, rhs: &$type) {
+                *self = (&*self).$method(rhs);
+            }
+        }
+        impl<$($generics)*> $assign for $type {
+            fn $assign_method(&mut self, rhs: $type) {
+                *self = (&*self).$method(&rhs);
+            }
+        }
+    };
+    (@each [$($generics:tt)*] $type:ty, by_value, $op:ident $method:ident $assign:ident $assign_method:ident) => {
+        impl<$($generics)*> $op<&$type> for $type {
+            type Output = $type;
+            fn $method(self, rhs: &$type) -> $type {
+                return self.$method(*rhs);
+            }
+        }
+        impl<$($generics)*> $op<$type> for &$type {
+            type Output = $type;
+            fn $method(self, rhs: $type) -> $type {
+                return (*self).$method(rhs);
+            }
+        }
+        impl<$($generics)*> $op<&$type> for &$type {
+            type Output = $type;
+            fn $method(self, rhs: &$type) -> $type {
+                return (*self).$method(*rhs);
+            }
+        }
+        impl<$($generics)*> $assign<&$type> for $type {
+            fn $assign_method(&mut self, rhs: &$type) {
+                *self = (*self).$method(*rhs);
+            }
+        }
+        impl<$($generics)*> $assign for $type {
+            fn $assign_method(&mut self, rhs: $type) {
+                *self = (*self).$method(rhs);
+            }
+        }
+    };
+}
+
+forward_operators!([] ModInt, by_ref);
+forward_operators!([const M: u64] ConstModInt<M>, by_value);
+
+impl<const M: u64> Default for ConstModInt<M> {
+    /// 0の剰余類(newを経由して法を検査する)
+    fn default() -> Self {
+        return ConstModInt::new(0);
+    }
+}
+
+impl<const M: u64> Zero for ConstModInt<M> {
+    fn zero() -> Self {
+        return ConstModInt::new(0);
+    }
+}
+
+impl<const M: u64> One for ConstModInt<M> {
+    fn one() -> Self {
+        return ConstModInt::new(1);
+    }
+}
+
+impl<const M: u64> Ring for ConstModInt<M> {}
+
+impl<const M: u64> From<u64> for ConstModInt<M> {
+    fn from(value: u64) -> Self {
+        return ConstModInt::new(value);
+    }
+}
+
+impl<const M: u64> fmt::Display for ConstModInt<M> {
+    /// 代表元を出力する
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return fmt::Display::fmt(&self.value, f);
+    }
+}
+
+#[cfg(test)]
+mod modint_test {
+    use super::{ConstModInt, ModInt, Modulus};
+    use crate::math_traits::*;
+    use crate::num::Integer;
+
+    type Mod7 = ConstModInt<7>;
+    const PRIME: u64 = 998_244_353;
+    type ModPrime = ConstModInt<PRIME>;
+
+    fn integer(s: &str) -> Integer {
+        return s.parse().unwrap();
+    }
+
+    #[test]
+    fn runtime_arithmetic_test() {
+        let modulus = Modulus::new(&integer("1000000007"));
+        let a = modulus.element(&integer("-3"));
+        let b = modulus.element(&integer("123456789012345"));
+        assert_eq!(a.residue(), &integer("1000000004"));
+        assert_eq!(b.residue(), &integer("788148153"));
+        assert_eq!((&a + &b).residue(), &integer("788148150"));
+        assert_eq!((&a - &b).residue(), &integer("211851851"));
+        assert_eq!((&a * &b).residue(), &integer("635555562"));
+        assert_eq!(&(&a / &b) * &b, a);
+        assert_eq!((-&a).residue(), &integer("3"));
+        let mut c = a.clone();
+        c += &b;
+        c *= b.clone();
+        assert_eq!(c, (&a + &b) * &b);
+        assert_eq!(format!("{}", modulus.element(&integer("-1"))), "1000000006");
+    }
+
+    #[test]
+    fn runtime_pow_inverse_test() {
+        let modulus = Modulus::new(&integer("12"));
+        assert_eq!(modulus.element(&integer("5")).inverse(), Some(modulus.element(&integer("5"))));
+        assert_eq!(modulus.element(&integer("4")).inverse(), None);
+        let big = Modulus::new(&integer("340282366920938463463374607431768211507"));
+        let x = big.element(&integer("987654321987654321"));
+        assert_eq!(x.pow(&integer("340282366920938463463374607431768211506")), big.element(&Integer::one()));
+        assert_eq!(&x.pow(&integer("-5")) * &x.pow(&integer("5")), big.element(&Integer::one()));
+        assert_eq!(x.pow(&Integer::zero()), big.element(&Integer::one()));
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus mismatch")]
+    fn modulus_mismatch_test() {
+        let _ = ModInt::new(&integer("1"), &integer("5")) + ModInt::new(&integer("1"), &integer("7"));
+    }
+
+    #[test]
+    fn runtime_sqrt_test() {
+        let modulus = Modulus::new(&integer("1000000009"));
+        for value in ["2", "5", "10", "123456789"] {
+            let x = modulus.element(&integer(value));
+            match x.sqrt() {
+                Some(root) => {
+                    assert_eq!(x.jacobi(), 1);
+                    assert_eq!(&root * &root, x);
+                }
+                None => assert_eq!(x.jacobi(), -1),
+            }
+        }
+        //p ≡ 1 (mod 2^k)の大きなkでもTonelli–Shanksが収束する
+        let modulus = Modulus::new(&integer("998244353"));
+        let x = modulus.element(&integer("5"));
+        assert_eq!(x.jacobi(), -1);
+        let square = &x * &x;
+        let root = square.sqrt().unwrap();
+        assert_eq!(&root * &root, square);
+        assert_eq!(root.residue(), &integer("5"));
+        //素数でない法では停止してNoneを返す
+        assert_eq!(ModInt::new(&integer("4"), &integer("9")).sqrt(), None);
+    }
+
+    #[test]
+    fn const_arithmetic_test() {
+        let a = Mod7::new(5);
+        let b = Mod7::new(4);
+        assert_eq!(a + b, Mod7::new(2));
+        assert_eq!(a - b, Mod7::new(1));
+        assert_eq!(b - a, Mod7::new(6));
+        assert_eq!(a * b, Mod7::new(6));
+        assert_eq!(a / b * b, a);
+        assert_eq!(-a, Mod7::new(2));
+        assert_eq!(a.inverse(), Some(Mod7::new(3)));
+        assert_eq!(Mod7::zero() - Mod7::one(), Mod7::new(6));
+        assert_eq!(Mod7::default(), Mod7::zero());
+        assert_eq!(ConstModInt::<{ u64::MAX }>::new(u64::MAX - 1) * ConstModInt::new(u64::MAX - 1), ConstModInt::new(1));
+        assert_eq!(ConstModInt::<10>::new(4).inverse(), None);
+        assert_eq!(format!("{}", Mod7::from(100)), "2");
+        assert_eq!(Mod7::from_integer(&integer("-1")), Mod7::new(6));
+        assert_eq!(Mod7::from_integer(&integer("14")), Mod7::zero());
+        assert_eq!(Mod7::from_integer(&Integer::zero()), Mod7::zero());
+    }
+
+    #[test]
+    fn const_pow_sqrt_test() {
+        let x = ModPrime::new(3);
+        assert_eq!(x.pow(PRIME - 1), ModPrime::one());
+        assert_eq!(x.pow(0), ModPrime::one());
+        assert_eq!(x.jacobi(), -1);
+        let square = ModPrime::new(123456);
+        let root = (square * square).sqrt().unwrap();
+        assert_eq!(root * root, square * square);
+        assert!(root.value() <= PRIME / 2);
+        assert_eq!(Mod7::new(3).sqrt(), None);
+        assert_eq!(Mod7::new(2).sqrt(), Some(Mod7::new(3)));
+        assert_eq!(Mod7::new(0).sqrt(), Some(Mod7::zero()));
+        let zero = Modulus::new(&integer("1000000009")).element(&Integer::zero());
+        assert_eq!(zero.sqrt(), Some(zero));
+    }
+}
@@ -3,18 +3,38 @@
 現時点で整数と有理数のみに対応しています。
 */
 use crate::arithmetic_util::*;
+use crate::digits::DigitVec;
 use crate::math_traits;
 use crate::math_traits::*;
 use crate::algorithm::*;
 use std::cmp::*;
+use std::fmt;
+use std::mem::size_of;
 use std::ops::*;
+use std::str::FromStr;
 
 /**
 本ライブラリにおける1桁の型
-ビット幅を変える必要がある場合は型を変更してください。(ただし整数に限る)
+ビット幅はcargoの機能で選択します。
+* 既定 - `u128`(積は64bitずつに分割して計算する)
+* `limb-u64` - `u64`(積は`u128`で計算する)
+* `limb-u32` - `u32`(積は`u64`で計算する)
+
+複数指定した場合は狭い方が優先されます。
 */
+#[cfg(feature = "limb-u32")]
+pub type Digit = u32;
+#[cfg(all(feature = "limb-u64", not(feature = "limb-u32")))]
+pub type Digit = u64;
+#[cfg(not(any(feature = "limb-u32", feature = "limb-u64")))]
 pub type Digit = u128;
 
+/// 1桁同士の積を保持できる2倍幅の型
+#[cfg(feature = "limb-u32")]
+pub(crate) type DoubleDigit = u64;
+#[cfg(all(feature = "limb-u64", not(feature = "limb-u32")))]
+pub(crate) type DoubleDigit = u128;
+
 /**
  * 符号型として新しく定義する。
  * これによって「負の0」問題を回避する
@@ -84,25 +104,25 @@ impl Div for &Sign {
 
 /// rfmライブラリにおける整数型の表現です。
 /// Integer expression in rfm library.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Integer {
     ///整数の絶対値
     ///この配列は絶対値を保持しており、補数表現をしてはならない。
-    number_data: Vec<Digit>,
+    pub(crate) number_data: DigitVec,
 
     ///符号管理フラグ
     ///trueのとき、負数となる。
-    sign: Sign,
+    pub(crate) sign: Sign,
 }
 
 impl Integer {
     pub fn from_number_slice(value: &[Digit], sign: Sign) -> Integer {
         let mut result_sign = sign;
-        let val_cutupzero = cut_upper_zeros(value);
-        if val_cutupzero.len() == 0 {
+        if value.is_empty() {
             panic!("empty is not allowed.");
         }
-        if val_cutupzero == &[0 as Digit] {
+        let val_cutupzero = &value[..significant_length(value)];
+        if val_cutupzero == [0 as Digit] {
             //絶対値がゼロの場合
             result_sign = Sign::Zero;
         } else if result_sign == Sign::Zero {
@@ -110,20 +130,285 @@ impl Integer {
             panic!("non zero value, but zero sign assigned.");
         }
         return Integer {
-            number_data: val_cutupzero.to_vec(),
+            number_data: DigitVec::from_slice(val_cutupzero),
             sign: result_sign,
         };
     }
 
     pub fn abs(&self) -> Integer {
-        return Integer::from_number_slice(&self.number_data, Sign::Positive);
+        return Integer::from_number_slice(
+            &self.number_data,
+            match self.sign {
+                Sign::Zero => Sign::Zero,
+                _ => Sign::Positive,
+            },
+        );
+    }
+
+    /// 符号を取得する
+    pub fn sign(&self) -> Sign {
+        return self.sign.clone();
+    }
+
+    /// 絶対値を2進数で表したときの桁数を返す
+    /// 0の場合は0を返す。
+    pub fn bit_length(&self) -> u64 {
+        if self.sign == Sign::Zero {
+            return 0;
+        }
+        return bit_length(&self.number_data);
+    }
+
+    /// 絶対値を2進数で表したときの末尾に続く0の個数を返す
+    /// 0の場合は0を返す。
+    pub fn trailing_zeros(&self) -> u64 {
+        let mut count = 0;
+        for digit in self.number_data.iter() {
+            if *digit != 0 {
+                return count + digit.trailing_zeros() as u64;
+            }
+            count += Digit::BITS as u64;
+        }
+        return 0;
+    }
+
+    /// 128bit符号付き整数に収まる場合のみ変換する(内部用)
+    pub(crate) fn to_i128(&self) -> Option<i128> {
+        let magnitude = self.magnitude_to_u128()?;
+        if magnitude > i128::MAX as u128 {
+            return None;
+        }
+        let magnitude = magnitude as i128;
+        return Some(match self.sign {
+            Sign::Negative => -magnitude,
+            _ => magnitude,
+        });
+    }
+
+    /// 絶対値が128bitに収まる場合のみ変換する(内部用)
+    pub(crate) fn magnitude_to_u128(&self) -> Option<u128> {
+        if self.bit_length() > 128 {
+            return None;
+        }
+        return Some(digits_to_u128(&self.number_data));
+    }
+
+    /**
+    指定した基数の表記で書かれた整数を読み込む
+    # Arguments
+    * s - 整数の表記(先頭の符号`+`/`-`を許容する)
+    * radix - 基数(2以上36以下)
+    # Panics
+    基数が2以上36以下でない場合
+     */
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Integer, ParseNumberError> {
+        if !(2..=36).contains(&radix) {
+            panic!("radix must be in [2, 36]");
+        }
+        if radix == 10 {
+            return s.parse();
+        }
+        let (negative, digits) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        if digits.is_empty() {
+            return Err(ParseNumberError::Empty);
+        }
+        let base = Integer::from_u32(radix);
+        let mut result = Integer::zero();
+        for c in digits.chars() {
+            let digit = c.to_digit(radix).ok_or(ParseNumberError::InvalidDigit)?;
+            result = &(&result * &base) + &Integer::from_u32(digit);
+        }
+        return Ok(match negative {
+            true => -result,
+            false => result,
+        });
+    }
+
+    /**
+    絶対値を下位バイトから並べたバイト列に変換する
+    # Returns
+    符号と、上位の0を除いたバイト列(0の場合は`[0]`)
+     */
+    pub fn to_bytes_le(&self) -> (Sign, Vec<u8>) {
+        let mut bytes: Vec<u8> = self.number_data.iter().flat_map(|digit| digit.to_le_bytes()).collect();
+        while bytes.len() > 1 && bytes.last() == Some(&0) {
+            bytes.pop();
+        }
+        return (self.sign(), bytes);
+    }
+
+    /// 絶対値を上位バイトから並べたバイト列に変換する
+    pub fn to_bytes_be(&self) -> (Sign, Vec<u8>) {
+        let (sign, mut bytes) = self.to_bytes_le();
+        bytes.reverse();
+        return (sign, bytes);
+    }
+
+    /**
+    下位バイトから並んだ絶対値と符号から整数を作成する
+    # Arguments
+    * sign - 符号(絶対値が0の場合は無視される)
+    * bytes - 絶対値のバイト列
+    # Panics
+    絶対値が0でないのに符号に`Sign::Zero`を指定した場合
+     */
+    pub fn from_bytes_le(sign: Sign, bytes: &[u8]) -> Integer {
+        let digits: Vec<Digit> = bytes
+            .chunks(size_of::<Digit>())
+            .map(|chunk| chunk.iter().rev().fold(0 as Digit, |acc, byte| (acc << 8) | *byte as Digit))
+            .collect();
+        return Integer::from_digits(sign, digits);
+    }
+
+    /// 上位バイトから並んだ絶対値と符号から整数を作成する
+    pub fn from_bytes_be(sign: Sign, bytes: &[u8]) -> Integer {
+        let reversed: Vec<u8> = bytes.iter().rev().copied().collect();
+        return Integer::from_bytes_le(sign, &reversed);
+    }
+
+    /// 2の補数表現で下位バイトから並べたバイト列に変換する(符号を表せる最小の長さ)
+    pub fn to_signed_bytes_le(&self) -> Vec<u8> {
+        let (sign, mut bytes) = self.to_bytes_le();
+        if sign == Sign::Negative {
+            twos_complement(&mut bytes);
+            if bytes.last().unwrap() & 0x80 == 0 {
+                bytes.push(0xff);
+            }
+            //符号の拡張にすぎない上位バイトを取り除く
+            while bytes.len() > 1 && bytes[bytes.len() - 1] == 0xff && bytes[bytes.len() - 2] & 0x80 != 0 {
+                bytes.pop();
+            }
+        } else if bytes.last().unwrap() & 0x80 != 0 {
+            bytes.push(0);
+        }
+        return bytes;
+    }
+
+    /// 2の補数表現で上位バイトから並べたバイト列に変換する
+    pub fn to_signed_bytes_be(&self) -> Vec<u8> {
+        let mut bytes = self.to_signed_bytes_le();
+        bytes.reverse();
+        return bytes;
+    }
+
+    /// 2の補数表現で下位バイトから並んだバイト列から整数を作成する(空の場合は0)
+    pub fn from_signed_bytes_le(bytes: &[u8]) -> Integer {
+        match bytes.last() {
+            Some(top) if top & 0x80 != 0 => {
+                let mut magnitude = bytes.to_vec();
+                twos_complement(&mut magnitude);
+                return Integer::from_bytes_le(Sign::Negative, &magnitude);
+            }
+            _ => return Integer::from_bytes_le(Sign::Positive, bytes),
+        }
+    }
+
+    /// 2の補数表現で上位バイトから並んだバイト列から整数を作成する
+    pub fn from_signed_bytes_be(bytes: &[u8]) -> Integer {
+        let reversed: Vec<u8> = bytes.iter().rev().copied().collect();
+        return Integer::from_signed_bytes_le(&reversed);
+    }
+
+    /// 絶対値を64bitごとに下位から並べる(0の場合は空)
+    pub fn to_u64_digits(&self) -> Vec<u64> {
+        let mut digits: Vec<u64> = self
+            .number_data
+            .iter()
+            .flat_map(|digit| digit.to_le_bytes())
+            .collect::<Vec<u8>>()
+            .chunks(size_of::<u64>())
+            .map(|chunk| chunk.iter().rev().fold(0u64, |acc, byte| (acc << 8) | *byte as u64))
+            .collect();
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        return digits;
+    }
+
+    /**
+    64bitごとに下位から並んだ絶対値と符号から整数を作成する
+    # Panics
+    絶対値が0でないのに符号に`Sign::Zero`を指定した場合
+     */
+    pub fn from_u64_digits(sign: Sign, digits: &[u64]) -> Integer {
+        let bytes: Vec<u8> = digits.iter().flat_map(|digit| digit.to_le_bytes()).collect();
+        return Integer::from_bytes_le(sign, &bytes);
+    }
+
+    /// 絶対値の各桁と符号から整数を作成する(絶対値が0なら符号を無視する)
+    fn from_digits(sign: Sign, digits: Vec<Digit>) -> Integer {
+        if digits.iter().all(|digit| *digit == 0) {
+            return Integer::zero();
+        }
+        return Integer::from_number_slice(&digits, sign);
+    }
+
+    /// 平方根の整数部分(床関数)を求める
+    /// # Panics
+    /// 負の数を与えた場合
+    pub fn sqrt(&self) -> Integer {
+        match self.sign {
+            Sign::Negative => panic!("Square root of negative number"),
+            Sign::Zero => return Integer::zero(),
+            Sign::Positive => {}
+        }
+        //ニュートン法で上から収束させる
+        let mut current = Integer::one() << (self.bit_length() as usize / 2 + 1);
+        loop {
+            let next = &(&current + &(self / &current)) >> 1;
+            if next >= current {
+                return current;
+            }
+            current = next;
+        }
+    }
+
+    /// n乗根の整数部分(0方向への切り捨て)を求める
+    /// # Panics
+    /// nが0の場合、またはnが偶数で負の数を与えた場合
+    pub fn nth_root(&self, n: u32) -> Integer {
+        assert!(n != 0, "zeroth root is undefined");
+        match self.sign {
+            Sign::Negative if n.is_multiple_of(2) => panic!("Even root of negative number"),
+            Sign::Negative => return -self.abs().nth_root(n),
+            Sign::Zero => return Integer::zero(),
+            Sign::Positive => {}
+        }
+        if n == 1 {
+            return self.clone();
+        }
+        //ニュートン法 x ← ((n-1)x + a / x^(n-1)) / n で上から収束させる
+        let degree = Integer::from_u32(n);
+        let lower_degree = Integer::from_u32(n - 1);
+        let mut current = Integer::one() << (self.bit_length() as usize / n as usize + 1);
+        loop {
+            let next = &(&(&lower_degree * &current) + &(self / &current.pow(lower_degree.clone()))) / &degree;
+            if next >= current {
+                return current;
+            }
+            current = next;
+        }
+    }
+}
+
+/// 下位バイトから並んだバイト列の2の補数をとる(桁あふれは捨てる)
+fn twos_complement(bytes: &mut [u8]) {
+    let mut carry = true;
+    for byte in bytes.iter_mut() {
+        let (value, overflow) = (!*byte).overflowing_add(carry as u8);
+        *byte = value;
+        carry = overflow;
     }
 }
 
 impl FromPrimitiveNumber for Integer {
     fn from_i128(val: i128) -> Self {
         return Integer {
-            number_data: vec![val.abs() as Digit],
+            number_data: digits_from_u128(val.unsigned_abs()),
             sign: match val {
                 1.. => Sign::Positive,
                 0 => Sign::Zero,
@@ -134,7 +419,7 @@ impl FromPrimitiveNumber for Integer {
 
     fn from_u128(val: u128) -> Self {
         return Integer {
-            number_data: vec![val],
+            number_data: digits_from_u128(val),
             sign: match val {
                 0 => Sign::Zero,
                 _ => Sign::Positive,
@@ -146,7 +431,9 @@ impl FromPrimitiveNumber for Integer {
 impl Add for &Integer {
     type Output = Integer;
     fn add(self, rhs: Self) -> Self::Output {
-        add_router(&self, &rhs)
+        let mut result = self.clone();
+        result.add_signed_assign(&rhs.number_data, &rhs.sign);
+        return result;
     }
 }
 
@@ -159,48 +446,39 @@ impl Add for Integer {
 
 impl AddAssign for Integer {
     fn add_assign(&mut self, other: Self) {
-        *self = add_router(self, &other);
+        self.add_signed_assign(&other.number_data, &other.sign);
     }
 }
 
-fn add_router(lhs: &Integer, rhs: &Integer) -> Integer {
-    return match (&lhs.sign, &rhs.sign) {
-        //正の整数同士の加算
-        (Sign::Positive, Sign::Positive) => Integer::from_number_slice(
-            &arbitrary_precision_add(&lhs.number_data, &rhs.number_data),
-            Sign::Positive,
-        ),
-        //負の整数同士
-        (Sign::Negative, Sign::Negative) => Integer::from_number_slice(
-            &arbitrary_precision_add(&lhs.number_data, &rhs.number_data),
-            Sign::Negative,
-        ),
-
-        //順番を入れ替えて減算として処理
-        (Sign::Negative, Sign::Positive) => {
-            let result_sub = arbitrary_precision_sub(&rhs.number_data, &lhs.number_data);
-            return Integer::from_number_slice(
-                &result_sub.0,
-                match result_sub.1 {
-                    true => Sign::Negative,
-                    false => Sign::Positive,
-                },
-            );
-        }
-        //相手がマイナスなので引き算と等価
-        (Sign::Positive, Sign::Negative) => {
-            let result_sub = arbitrary_precision_sub(&lhs.number_data, &rhs.number_data);
-            return Integer::from_number_slice(
-                &result_sub.0,
-                match result_sub.1 {
-                    true => Sign::Negative,
-                    false => Sign::Positive,
-                },
-            );
+impl AddAssign<&Integer> for Integer {
+    fn add_assign(&mut self, other: &Integer) {
+        self.add_signed_assign(&other.number_data, &other.sign);
+    }
+}
+
+impl Integer {
+    /// 符号付きの絶対値をその場で加算する(容量が足りる限り再確保しない)
+    fn add_signed_assign(&mut self, rhs: &[Digit], rhs_sign: &Sign) {
+        if *rhs_sign == Sign::Zero {
+            return;
         }
-        (Sign::Zero, _) => rhs.clone(),
-        (_, Sign::Zero) => lhs.clone(),
-    };
+        if self.sign == Sign::Zero {
+            self.number_data.clear();
+            self.number_data.extend_from_slice(rhs);
+            self.sign = rhs_sign.clone();
+            return;
+        }
+        if self.sign == *rhs_sign {
+            add_assign(&mut self.number_data, rhs);
+            return;
+        }
+        let negative = sub_assign(&mut self.number_data, rhs);
+        if self.number_data[..] == [0 as Digit] {
+            self.sign = Sign::Zero;
+        } else if negative {
+            self.sign = -&self.sign;
+        }
+    }
 }
 
 impl Neg for &Integer {
@@ -214,14 +492,19 @@ impl Neg for Integer {
     type Output = Integer;
 
     fn neg(self) -> Self::Output {
-        return Integer::from_number_slice(&self.number_data, -self.sign);
+        return Integer {
+            number_data: self.number_data,
+            sign: -self.sign,
+        };
     }
 }
 
 impl Sub for &Integer {
     type Output = Integer;
     fn sub(self, rhs: Self) -> Self::Output {
-        return add_router(&self, &-rhs);
+        let mut result = self.clone();
+        result.add_signed_assign(&rhs.number_data, &-&rhs.sign);
+        return result;
     }
 }
 
@@ -234,7 +517,13 @@ impl Sub for Integer {
 
 impl SubAssign for Integer {
     fn sub_assign(&mut self, rhs: Self) {
-        *self = add_router(self, &-rhs);
+        self.add_signed_assign(&rhs.number_data, &-&rhs.sign);
+    }
+}
+
+impl SubAssign<&Integer> for Integer {
+    fn sub_assign(&mut self, rhs: &Integer) {
+        self.add_signed_assign(&rhs.number_data, &-&rhs.sign);
     }
 }
 
@@ -245,17 +534,20 @@ impl Mul for &Integer {
         if sign == Sign::Zero {
             return Integer::zero();
         }
-        let one = Integer::one();
-
-        if rhs.abs() == one {
-            return Integer::from_number_slice(&self.number_data, sign);
-        } else if self.abs() == one {
-            return Integer::from_number_slice(&rhs.number_data, sign);
+        //どちらかが1桁なら、もう一方の複製にその場で掛ける
+        let (long, short) = match self.number_data.len() < rhs.number_data.len() {
+            true => (rhs, self),
+            false => (self, rhs),
+        };
+        if short.number_data.len() == 1 {
+            let mut number_data = long.number_data.clone();
+            mul_small_assign(&mut number_data, short.number_data[0]);
+            return Integer { number_data, sign };
         }
-        return Integer::from_number_slice(
-            &arbitrary_precision_mul(&self.number_data, &rhs.number_data),
+        return Integer {
+            number_data: DigitVec::from(arbitrary_precision_mul(&self.number_data, &rhs.number_data)),
             sign,
-        );
+        };
     }
 }
 
@@ -268,82 +560,47 @@ impl Mul for Integer {
 
 impl MulAssign for Integer {
     fn mul_assign(&mut self, rhs: Self) {
+        *self *= &rhs;
+    }
+}
+
+impl MulAssign<&Integer> for Integer {
+    /// 右辺が1桁の場合はその場で乗算する
+    fn mul_assign(&mut self, rhs: &Integer) {
+        if rhs.number_data.len() > 1 {
+            *self = &*self * rhs;
+            return;
+        }
+        mul_small_assign(&mut self.number_data, rhs.number_data[0]);
         self.sign = &self.sign * &rhs.sign;
-        self.number_data = arbitrary_precision_mul(&self.number_data, &rhs.number_data);
+    }
+}
+
+impl PartialOrd for Integer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return Some(self.cmp(other));
     }
 }
 
 impl Ord for Integer {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        let result = self - other;
-        match result.sign {
-            Sign::Positive => Ordering::Greater,
-            Sign::Zero => Ordering::Equal,
-            Sign::Negative => Ordering::Less,
+        if self.sign != other.sign {
+            return self.sign.cmp(&other.sign);
         }
+        let result = compare_abs(&self.number_data, &other.number_data);
+        return match self.sign {
+            Sign::Negative => result.reverse(),
+            _ => result,
+        };
     }
 }
 
 impl Div for &Integer {
     type Output = Integer;
+    /// 0方向に切り捨てる除算(プリミティブ整数と同じ挙動)
     fn div(self, rhs: Self) -> Self::Output {
-
-        let sign: Sign = &self.sign / &rhs.sign;
-        if sign == Sign::Zero{
-            return Integer::zero();
-        }else if rhs.abs() == Integer::one() {
-            return Integer::from_number_slice(&self.number_data, sign);
-        }else if self.abs() == Integer::one() {
-            return Integer::from_number_slice(&rhs.number_data,sign);
-        }
-        let (inverse_num, inverse_exp) = calculate_inverse(rhs);
-        let mut result_value = self * &inverse_num;
-        result_value
-            .number_data
-            .drain(0..inverse_exp.abs() as usize); //桁繰り下げ
-        result_value.sign = sign;
-        return result_value;
-    }
-}
-
-fn calculate_inverse(rhs: &Integer) -> (Integer, i128) {
-    let calc_number = rhs.number_data.len() + 1;
-    //有効数字を1多くして処理、収束したら最下桁を消す。
-    let src = rhs.abs();
-    let mut predict = (Integer::from_u128(1 as Digit), -&(calc_number as i128) + 1);
-    loop {
-        let mul_predict_src = (&predict.0 * &src, predict.1);
-        //2を求める
-        let mut two_vec = vec![0 as Digit; (0 - mul_predict_src.1) as usize];
-        two_vec.push(2);
-        let predict_step2 = (
-            Integer::from_number_slice(&two_vec, Sign::Positive) - mul_predict_src.0,
-            mul_predict_src.1,
-        );
-        let mut next_predict = (&predict_step2.0 * &predict.0, &predict_step2.1 + &predict.1);
-        let next_predict_len = next_predict.0.number_data.len();
-
-        if next_predict_len > calc_number {
-            let diff: usize = next_predict_len - calc_number;
-            next_predict.0.number_data.drain(0..diff);
-            next_predict.1 += diff as i128; //drainしたので差分調整
-        }
-
-        if next_predict.0 == predict.0 {
-            //桁を落としているため、最終的に収束することによって等価演算子による演算は問題ない。
-            break;
-        }
-
-        predict = next_predict;
-    }
-    //四捨五入相当の操作を実行(乗算して試すより安定チェックで1桁多く取っているので乗算する必要なし)
-    if predict.0.number_data[0] >= (1 << (Digit::BITS / 2)) {
-        predict.0.number_data[1] += 1;
+        return self.div_rem(rhs).0;
     }
-    predict.0.number_data.remove(0);
-    predict.1 += 1; //差分調整
-    debug_assert!(predict.1 <= 0);
-    predict
 }
 
 impl Div for Integer {
@@ -355,8 +612,9 @@ impl Div for Integer {
 
 impl Rem for &Integer {
     type Output = Integer;
+    /// 余りの符号は被除数に従う(プリミティブ整数と同じ挙動)
     fn rem(self, rhs: Self) -> Self::Output {
-        return self - &(&(self / &rhs) * &rhs);
+        return self.div_rem(rhs).1;
     }
 }
 
@@ -369,12 +627,60 @@ impl Rem for Integer {
 
 impl DivRem for Integer {
     fn div_rem(&self, rhs: &Self) -> (Self, Self) {
-        let div = self / rhs;
-        let rem = self - &(&div * &rhs);
+        let sign: Sign = &self.sign / &rhs.sign;
+        if sign == Sign::Zero {
+            return (Integer::zero(), Integer::zero());
+        }
+        if self.number_data.len() == 1 && rhs.number_data.len() == 1 {
+            let (lhs_digit, rhs_digit) = (self.number_data[0], rhs.number_data[0]);
+            let div = Integer::from_number_slice(&[lhs_digit / rhs_digit], sign);
+            let rem = Integer::from_number_slice(&[lhs_digit % rhs_digit], self.sign.clone());
+            return (div, rem);
+        }
+        let (quotient, remain) = arbitrary_precision_div_rem(&self.number_data, &rhs.number_data);
+        let div = Integer::from_number_slice(&quotient, sign);
+        let rem = Integer::from_number_slice(&remain, self.sign.clone());
         return (div, rem);
     }
 }
 
+impl Shl<usize> for &Integer {
+    type Output = Integer;
+    /// 2のべき乗倍
+    fn shl(self, rhs: usize) -> Self::Output {
+        return Integer::from_number_slice(&shift_left_bits(&self.number_data, rhs), self.sign.clone());
+    }
+}
+
+impl Shl<usize> for Integer {
+    type Output = Integer;
+    fn shl(self, rhs: usize) -> Self::Output {
+        return &self << rhs;
+    }
+}
+
+impl Shr<usize> for &Integer {
+    type Output = Integer;
+    /// 2のべき乗で割る
+    /// プリミティブ整数と同様に負の無限大方向へ丸める。
+    fn shr(self, rhs: usize) -> Self::Output {
+        return match self.sign {
+            Sign::Negative => {
+                let magnitude = &(&self.abs() - &Integer::one()) >> rhs;
+                -(magnitude + Integer::one())
+            }
+            _ => Integer::from_number_slice(&shift_right_bits(&self.number_data, rhs), self.sign.clone()),
+        };
+    }
+}
+
+impl Shr<usize> for Integer {
+    type Output = Integer;
+    fn shr(self, rhs: usize) -> Self::Output {
+        return &self >> rhs;
+    }
+}
+
 /// Integer型の単位元0を定義する
 impl math_traits::Zero for Integer {
     fn zero() -> Integer {
@@ -391,18 +697,25 @@ impl math_traits::One for Integer {
 
 impl math_traits::EvenOdd for Integer {
     fn is_even(&self) -> bool {
-        return 0 == self.number_data[0] % 2;
+        return self.number_data[0].is_multiple_of(2);
     }
 
     fn is_odd(&self) -> bool {
-        return 0 != self.number_data[0] % 2;
+        return !self.number_data[0].is_multiple_of(2);
     }
 }
 
 impl math_traits::Pow for Integer {
+    /// # Panics
+    /// 指数が負の場合
     fn pow(&self, exp: Self) -> Self {
         let two = Integer::from_i128(2);
         let one = Integer::one();
+        match exp.sign {
+            Sign::Negative => panic!("Negative exponent is not supported for Integer"),
+            Sign::Zero => return one,
+            Sign::Positive => {}
+        }
         if exp == one {
             return self.clone();
         }
@@ -410,29 +723,149 @@ impl math_traits::Pow for Integer {
         if exp.is_even() {
             return &powered * &powered;
         } else {
-            return &(&powered * &powered) * &self; //奇数の場合は1個下の偶数に+1(指数法則)
+            return &(&powered * &powered) * self; //奇数の場合は1個下の偶数に+1(指数法則)
+        }
+    }
+}
+
+impl math_traits::Ring for Integer {}
+
+impl math_traits::EuclideanDomain for Integer {
+    /// 最大公約数(常に0以上)
+    fn gcd(&self, other: &Self) -> Self {
+        return gcd(self, other);
+    }
+
+    /// 最小公倍数(常に0以上)
+    fn lcm(&self, other: &Self) -> Self {
+        if self.sign == Sign::Zero || other.sign == Sign::Zero {
+            return Integer::zero();
         }
+        return (&(self / &gcd(self, other)) * other).abs();
     }
 }
 
-/*
-impl std::fmt::Display for &Integer{
+impl math_traits::Signed for Integer {
+    fn abs(&self) -> Self {
+        return Integer::abs(self);
+    }
+
+    fn signum(&self) -> Self {
+        return match self.sign {
+            Sign::Negative => Integer::from_i128(-1),
+            Sign::Zero => Integer::zero(),
+            Sign::Positive => Integer::one(),
+        };
+    }
+
+    fn is_positive(&self) -> bool {
+        return self.sign == Sign::Positive;
+    }
+
+    fn is_negative(&self) -> bool {
+        return self.sign == Sign::Negative;
+    }
+}
+
+/// 10進数変換時の区切り(u64に収まる最大の10のべき乗)
+const DECIMAL_CHUNK: u64 = 10_000_000_000_000_000_000;
+const DECIMAL_CHUNK_DIGITS: usize = 19;
+
+impl fmt::Display for Integer {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        //下位から19桁ずつ取り出す
+        let mut chunks: Vec<u64> = Vec::new();
+        let mut data = self.number_data.to_vec();
+        loop {
+            let (quotient, remain) = arbitrary_precision_div_small(&data, DECIMAL_CHUNK);
+            chunks.push(remain);
+            if quotient == [0 as Digit] {
+                break;
+            }
+            data = quotient;
+        }
+        let mut digits = chunks.pop().unwrap_or(0).to_string();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:0width$}", chunk, width = DECIMAL_CHUNK_DIGITS));
+        }
+        return f.pad_integral(self.sign != Sign::Negative, "", &digits);
+    }
+}
 
+/// 文字列から数値への変換に失敗した場合のエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseNumberError {
+    /// 空文字列
+    Empty,
+    /// 数字として解釈できない文字を含む
+    InvalidDigit,
+    /// 分母に0を指定した
+    DivideByZero,
+}
+
+impl fmt::Display for ParseNumberError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            ParseNumberError::Empty => write!(f, "cannot parse number from empty string"),
+            ParseNumberError::InvalidDigit => write!(f, "invalid digit found in string"),
+            ParseNumberError::DivideByZero => write!(f, "denominator must not be zero"),
+        };
+    }
+}
+
+impl std::error::Error for ParseNumberError {}
+
+impl FromStr for Integer {
+    type Err = ParseNumberError;
+    /// 10進数表記の整数を読み込む(先頭の符号`+`/`-`を許容する)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        if digits.is_empty() {
+            return Err(ParseNumberError::Empty);
+        }
+        if !digits.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(ParseNumberError::InvalidDigit);
+        }
+        //上位から19桁ずつ取り込む
+        let chunk_base = Integer::from_u64(DECIMAL_CHUNK);
+        let head = digits.len() % DECIMAL_CHUNK_DIGITS;
+        let mut result = Integer::zero();
+        let mut position = 0;
+        while position < digits.len() {
+            let end = match position {
+                0 if head != 0 => head,
+                _ => position + DECIMAL_CHUNK_DIGITS,
+            };
+            let chunk: u64 = digits[position..end].parse().map_err(|_| ParseNumberError::InvalidDigit)?;
+            let scale = match end - position {
+                DECIMAL_CHUNK_DIGITS => chunk_base.clone(),
+                width => Integer::from_u64(10u64.pow(width as u32)),
+            };
+            result = &(&result * &scale) + &Integer::from_u64(chunk);
+            position = end;
+        }
+        return Ok(match negative {
+            true => -result,
+            false => result,
+        });
     }
 }
-*/
 
 /**rfmライブラリにおける有理数型の表現です。
 
  有理数は2つの整数型を組み合わせた分数で表現されます。
+ 分数は常に既約分数に約分され、符号は分子が持ちます(分母は常に正)。
  # Panics
  ゼロ除算となるような分数を作成しようとした場合panic!により停止します。
 */
-#[derive(Debug, PartialEq, Eq, PartialOrd, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Rational {
-    positive: Integer,
-    divider: Integer,
+    pub(crate) positive: Integer,
+    pub(crate) divider: Integer,
 }
 
 impl Rational {
@@ -442,7 +875,7 @@ impl Rational {
     * positive - 分子
     * divider - 分母
     # Returns
-    指定された引数で構成される分数を返す。
+    指定された引数で構成される分数を既約分数に約分して返す。
     # Panics
     dividerに0を指定した場合、ゼロ除算の扱いとなり、処理を中止します。
      */
@@ -450,30 +883,198 @@ impl Rational {
         if divider == &Integer::from_u128(0) {
             panic!("Divide by zero"); //ゼロ除算防止
         }
-        return Rational {
-            positive: positive.clone(),
-            divider: divider.clone(),
+        let mut result = match divider.sign {
+            Sign::Negative => Rational {
+                positive: -positive,
+                divider: -divider,
+            },
+            _ => Rational {
+                positive: positive.clone(),
+                divider: divider.clone(),
+            },
         };
+        result.reduction();
+        return result;
     }
 
     /// 整数を実数型に変換する
     /// # Arguments
     /// * val - 整数
+    ///
     /// このメソッドは引数に指定された整数と等価な実数値を返します。
+    #[deprecated(note = "use `Rational::from` instead")]
     pub fn from_intager(val: &Integer) -> Rational {
-        return Rational {
-            positive: val.clone(),
-            divider: Integer::one(),
-        };
+        return Rational::from(val);
     }
 
     pub fn reduction(&mut self){
+        if self.positive == Integer::zero() {
+            self.divider = Integer::one();
+            return;
+        }
         let divider = gcd(&self.positive,&self.divider);
         self.positive = &self.positive/&divider;
         self.divider = &self.divider/&divider;
     }
 }
 
+/// 有理数を整数に丸める方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// 負の無限大方向(床関数)
+    Floor,
+    /// 正の無限大方向(天井関数)
+    Ceiling,
+    /// 0方向(切り捨て)
+    TowardZero,
+    /// 0から離れる方向(切り上げ)
+    AwayFromZero,
+    /// 最も近い整数(ちょうど中間なら0から離れる方向、いわゆる四捨五入)
+    HalfAwayFromZero,
+    /// 最も近い整数(ちょうど中間なら偶数、いわゆる銀行家の丸め)
+    HalfEven,
+}
+
+impl Rational {
+    /// 分子を取得する(符号は分子が持つ)
+    pub fn numer(&self) -> &Integer {
+        return &self.positive;
+    }
+
+    /// 分母を取得する(常に正)
+    pub fn denom(&self) -> &Integer {
+        return &self.divider;
+    }
+
+    /// (分子, 分母)に分解する
+    pub fn into_parts(self) -> (Integer, Integer) {
+        return (self.positive, self.divider);
+    }
+
+    /// 逆数を求める
+    /// # Panics
+    /// 0の逆数を求めた場合
+    pub fn recip(&self) -> Rational {
+        return match self.positive.sign {
+            Sign::Zero => panic!("Divide by zero"),
+            Sign::Negative => Rational {
+                positive: -&self.divider,
+                divider: -&self.positive,
+            },
+            Sign::Positive => Rational {
+                positive: self.divider.clone(),
+                divider: self.positive.clone(),
+            },
+        };
+    }
+
+    /// 絶対値を求める
+    pub fn abs(&self) -> Rational {
+        return Rational {
+            positive: self.positive.abs(),
+            divider: self.divider.clone(),
+        };
+    }
+
+    /// 符号を-1, 0, 1のいずれかで返す
+    pub fn signum(&self) -> Rational {
+        return match self.positive.sign {
+            Sign::Negative => Rational::from_i128(-1),
+            Sign::Zero => Rational::zero(),
+            Sign::Positive => Rational::one(),
+        };
+    }
+
+    /// 整数かどうか
+    pub fn is_integer(&self) -> bool {
+        return self.divider == Integer::one();
+    }
+
+    /// 指定した方法で整数に丸める
+    pub fn to_integer(&self, mode: RoundingMode) -> Integer {
+        let (quotient, remain) = self.positive.div_rem(&self.divider);
+        if remain.sign == Sign::Zero {
+            return quotient;
+        }
+        //0から離れる方向の隣の整数
+        let away = match remain.sign {
+            Sign::Negative => &quotient - &Integer::one(),
+            _ => &quotient + &Integer::one(),
+        };
+        let is_negative = self.positive.sign == Sign::Negative;
+        //余りの2倍と分母を比べて中間より遠いかを判定する
+        let half = compare_abs(&(&remain << 1).number_data, &self.divider.number_data);
+        return match (mode, half) {
+            (RoundingMode::Floor, _) if is_negative => away,
+            (RoundingMode::Ceiling, _) if !is_negative => away,
+            (RoundingMode::Floor | RoundingMode::Ceiling | RoundingMode::TowardZero, _) => quotient,
+            (RoundingMode::AwayFromZero, _) => away,
+            (_, Ordering::Greater) => away,
+            (_, Ordering::Less) => quotient,
+            (RoundingMode::HalfAwayFromZero, Ordering::Equal) => away,
+            (RoundingMode::HalfEven, Ordering::Equal) => match quotient.is_even() {
+                true => quotient,
+                false => away,
+            },
+        };
+    }
+
+    /**
+    整数乗を求める(負の指数は逆数の累乗)
+    # Panics
+    0を負の指数で累乗した場合
+     */
+    pub fn pow(&self, exp: i64) -> Rational {
+        let exponent = Integer::from_u64(exp.unsigned_abs());
+        //既約分数の累乗は既約なので約分は不要
+        let positive = self.positive.pow(exponent.clone());
+        let divider = self.divider.pow(exponent);
+        if exp >= 0 {
+            return Rational { positive, divider };
+        }
+        return match positive.sign {
+            Sign::Zero => panic!("Divide by zero"),
+            Sign::Negative => Rational {
+                positive: -divider,
+                divider: -positive,
+            },
+            Sign::Positive => Rational {
+                positive: divider,
+                divider: positive,
+            },
+        };
+    }
+
+    /// 分子と分母がともに平方数の場合のみ平方根を返す
+    pub fn checked_sqrt(&self) -> Option<Rational> {
+        return self.nth_root_exact(2);
+    }
+
+    /**
+    n乗根が有理数となる場合のみ、その値を返す
+
+    nが偶数の場合は0以上の根を返し、負の数に対してはNoneを返す。
+    # Panics
+    nが0の場合
+     */
+    pub fn nth_root_exact(&self, n: u32) -> Option<Rational> {
+        assert!(n != 0, "zeroth root is undefined");
+        if self.positive.sign == Sign::Negative && n.is_multiple_of(2) {
+            return None;
+        }
+        let exponent = Integer::from_u32(n);
+        let positive = self.positive.nth_root(n);
+        if positive.pow(exponent.clone()) != self.positive {
+            return None;
+        }
+        let divider = self.divider.nth_root(n);
+        if divider.pow(exponent) != self.divider {
+            return None;
+        }
+        return Some(Rational { positive, divider });
+    }
+}
+
 impl Add for &Rational {
     type Output = Rational;
     fn add(self, rhs: Self) -> Self::Output {
@@ -493,8 +1094,24 @@ impl Add for Rational {
 
 impl AddAssign for Rational {
     fn add_assign(&mut self, rhs: Self) {
-        self.positive = &self.positive * &rhs.divider + &rhs.positive * &self.divider;
-        self.divider = &self.divider * &rhs.divider;
+        *self = &*self + &rhs;
+    }
+}
+
+impl Neg for &Rational {
+    type Output = Rational;
+    fn neg(self) -> Self::Output {
+        return Rational {
+            positive: -&self.positive,
+            divider: self.divider.clone(),
+        };
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+    fn neg(self) -> Self::Output {
+        return -&self;
     }
 }
 
@@ -517,8 +1134,7 @@ impl Sub for Rational {
 
 impl SubAssign for Rational {
     fn sub_assign(&mut self, rhs: Self) {
-        self.positive = &self.positive * &rhs.divider - &rhs.positive * &self.divider;
-        self.divider = &self.divider * &rhs.divider;
+        *self = &*self - &rhs;
     }
 }
 
@@ -541,8 +1157,7 @@ impl Mul for Rational {
 
 impl MulAssign for Rational {
     fn mul_assign(&mut self, rhs: Self) {
-        self.positive = &self.positive * &rhs.positive;
-        self.positive = &self.divider * &rhs.divider;
+        *self = &*self * &rhs;
     }
 }
 
@@ -559,7 +1174,7 @@ impl Div for &Rational {
 impl Div for Rational {
     type Output = Rational;
     fn div(self, rhs: Self) -> Self::Output {
-        return &self * &rhs;
+        return &self / &rhs;
     }
 }
 
@@ -568,26 +1183,172 @@ impl DivAssign for Rational {
         if rhs.positive == Integer::from_u128(0) {
             panic!("Divide by zero")
         }
-        self.positive = &self.positive * &rhs.divider;
-        self.divider = &self.divider * &rhs.positive;
+        *self = &*self / &rhs;
+    }
+}
+
+impl Rem for &Rational {
+    type Output = Rational;
+    /// 商を0方向に切り捨てたときの余り(符号は被除数に従う)
+    fn rem(self, rhs: Self) -> Self::Output {
+        let quotient = self / rhs;
+        let truncated = Rational::from(&(&quotient.positive / &quotient.divider));
+        return self - &(rhs * &truncated);
+    }
+}
+
+impl Rem for Rational {
+    type Output = Rational;
+    fn rem(self, rhs: Self) -> Self::Output {
+        return &self % &rhs;
+    }
+}
+
+impl RemAssign for Rational {
+    fn rem_assign(&mut self, rhs: Self) {
+        *self = &*self % &rhs;
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return Some(self.cmp(other));
     }
 }
 
 impl Ord for Rational {
     fn cmp(&self, other: &Self) -> Ordering {
-        let result = self - other;
-        return match result.positive.sign * result.divider.sign {
-            Sign::Positive => Ordering::Greater,
-            Sign::Negative => Ordering::Less,
-            Sign::Zero => Ordering::Equal,
+        //分母は常に正なので、通分した分子を比較すればよい
+        return (&self.positive * &other.divider).cmp(&(&other.positive * &self.divider));
+    }
+}
+
+impl From<&Integer> for Rational {
+    fn from(value: &Integer) -> Self {
+        return Rational {
+            positive: value.clone(),
+            divider: Integer::one(),
         };
     }
 }
 
+impl From<Integer> for Rational {
+    fn from(value: Integer) -> Self {
+        return Rational {
+            positive: value,
+            divider: Integer::one(),
+        };
+    }
+}
+
+/// プリミティブ整数からの変換(`i128`/`u128`を経由するため、同じ型へのキャストに対する警告は抑制する)
+macro_rules! from_primitive {
+    ($target:ty, $convert:ident, $via:ty, $($primitive:ty),*) => {$(
+        #[allow(clippy::unnecessary_cast)]
+        impl From<$primitive> for $target {
+            fn from(value: $primitive) -> Self {
+                return <$target>::$convert(value as $via);
+            }
+        }
+    )*};
+}
+
+from_primitive!(Integer, from_i128, i128, i8, i16, i32, i64, i128, isize);
+from_primitive!(Integer, from_u128, u128, u8, u16, u32, u64, u128, usize);
+from_primitive!(Rational, from_i128, i128, i8, i16, i32, i64, i128, isize);
+from_primitive!(Rational, from_u128, u128, u8, u16, u32, u64, u128, usize);
+
+impl FromPrimitiveNumber for Rational {
+    fn from_i128(val: i128) -> Self {
+        return Rational::from(&Integer::from_i128(val));
+    }
+
+    fn from_u128(val: u128) -> Self {
+        return Rational::from(&Integer::from_u128(val));
+    }
+}
+
+/// Rational型の単位元0を定義する
+impl math_traits::Zero for Rational {
+    fn zero() -> Rational {
+        return Rational::from(&Integer::zero());
+    }
+}
+
+/// Rational型の単位元1を定義する
+impl math_traits::One for Rational {
+    fn one() -> Rational {
+        return Rational::from(&Integer::one());
+    }
+}
+
+impl math_traits::Ring for Rational {}
+
+impl math_traits::Field for Rational {
+    fn recip(&self) -> Self {
+        return Rational::recip(self);
+    }
+}
+
+impl math_traits::Signed for Rational {
+    fn is_positive(&self) -> bool {
+        return self.positive.sign == Sign::Positive;
+    }
+
+    fn is_negative(&self) -> bool {
+        return self.positive.sign == Sign::Negative;
+    }
+}
+
+impl fmt::Display for Rational {
+    /// `分子/分母`の形式で出力する(分母が1の場合は分子のみ)
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.divider == Integer::one() {
+            return fmt::Display::fmt(&self.positive, f);
+        }
+        return f.pad(&format!("{}/{}", self.positive, self.divider));
+    }
+}
+
+impl FromStr for Rational {
+    type Err = ParseNumberError;
+    /// `分子/分母`、整数、または`-1.25`のような小数表記を読み込む
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((positive, divider)) = s.split_once('/') {
+            let divider: Integer = divider.parse()?;
+            if divider == Integer::zero() {
+                return Err(ParseNumberError::DivideByZero);
+            }
+            return Ok(Rational::new(&positive.parse()?, &divider));
+        }
+        if let Some((integer_part, fraction_part)) = s.split_once('.') {
+            if fraction_part.is_empty() || !fraction_part.bytes().all(|c| c.is_ascii_digit()) {
+                return Err(ParseNumberError::InvalidDigit);
+            }
+            let negative = integer_part.starts_with('-');
+            let integer_part: Integer = match integer_part {
+                "" | "-" | "+" => Integer::zero(),
+                _ => integer_part.parse()?,
+            };
+            let scale = Integer::from_u128(10).pow(Integer::from_u128(fraction_part.len() as u128));
+            let fraction: Integer = fraction_part.parse()?;
+            let magnitude = &(&integer_part.abs() * &scale) + &fraction;
+            return Ok(Rational::new(
+                &match negative {
+                    true => -magnitude,
+                    false => magnitude,
+                },
+                &scale,
+            ));
+        }
+        return Ok(Rational::from(s.parse::<Integer>()?));
+    }
+}
+
 #[cfg(test)]
 mod integer_test {
     use super::{Digit, Integer, Sign};
-    use crate::math_traits::{FromPrimitiveNumber, Pow};
+    use crate::math_traits::{DivRem, FromPrimitiveNumber, One, Pow};
 
     #[test]
     fn div_test() {
@@ -628,14 +1389,263 @@ mod integer_test {
         );
     }
 
+    #[test]
+    fn pow_zero_test() {
+        assert_eq!(Integer::from_u128(1), Integer::from_u128(7).pow(Integer::from_u128(0)));
+        assert_eq!(Integer::from_u128(1), Integer::from_u128(0).pow(Integer::from_u128(0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn pow_negative_test() {
+        Integer::from_u128(2).pow(Integer::from_i128(-1));
+    }
+
+    #[test]
+    fn div_negative_test() {
+        assert_eq!(
+            Integer::from_i128(-3),
+            Integer::from_i128(-20) / Integer::from_i128(6)
+        );
+        assert_eq!(
+            Integer::from_i128(-2),
+            Integer::from_i128(-20) % Integer::from_i128(6)
+        );
+        assert_eq!(
+            Integer::from_i128(0),
+            Integer::from_i128(5) / Integer::from_i128(-7)
+        );
+    }
+
+    #[test]
+    fn div_multiple_digit_test() {
+        let a = Integer::from_number_slice(&[123, 456, 789], Sign::Positive);
+        let b = Integer::from_number_slice(&[Digit::MAX, 1], Sign::Positive);
+        let c = Integer::from_u128(98765);
+        let (quotient, remain) = (&(&a * &b) + &c).div_rem(&b);
+        assert_eq!(quotient, a);
+        assert_eq!(remain, c);
+    }
+
+    #[test]
+    fn cmp_test() {
+        assert!(Integer::from_i128(-5) < Integer::from_i128(3));
+        assert!(Integer::from_i128(-5) < Integer::from_i128(-3));
+        assert!(Integer::from_number_slice(&[0, 1], Sign::Positive) > Integer::from_number_slice(&[Digit::MAX], Sign::Positive));
+    }
+
+    #[test]
+    fn shift_test() {
+        assert_eq!(
+            Integer::from_number_slice(&[0, 0, 8], Sign::Positive),
+            Integer::from_u128(1) << (2 * Digit::BITS as usize + 3)
+        );
+        assert_eq!(Integer::from_i128(-7) >> 1, Integer::from_i128(-4));
+    }
+
+    #[test]
+    fn bit_length_test() {
+        assert_eq!(Integer::from_u128(0).bit_length(), 0);
+        assert_eq!(Integer::from_i128(-255).bit_length(), 8);
+        assert_eq!((Integer::from_u128(1) << 200).bit_length(), 201);
+    }
+
+    #[test]
+    fn sqrt_test() {
+        assert_eq!(Integer::from_u128(99).sqrt(), Integer::from_u128(9));
+        assert_eq!(Integer::from_u128(100).sqrt(), Integer::from_u128(10));
+    }
+
+    #[test]
+    fn nth_root_test() {
+        assert_eq!(Integer::from_u128(26).nth_root(3), Integer::from_u128(2));
+        assert_eq!(Integer::from_u128(27).nth_root(3), Integer::from_u128(3));
+        assert_eq!(Integer::from_i128(-28).nth_root(3), Integer::from_i128(-3));
+        let big = Integer::from_u128(12345678901234567890).pow(Integer::from_u128(7));
+        assert_eq!(big.nth_root(7), Integer::from_u128(12345678901234567890));
+        assert_eq!((&big - &Integer::one()).nth_root(7), Integer::from_u128(12345678901234567889));
+        assert_eq!(Integer::from_u128(5).nth_root(1), Integer::from_u128(5));
+    }
+
+    #[test]
+    fn string_test() {
+        let text = "-123456789012345678901234567890123456789012345678901234567890";
+        let value: Integer = text.parse().unwrap();
+        assert_eq!(value.to_string(), text);
+        assert_eq!(Integer::from_i128(0).to_string(), "0");
+        assert_eq!("12a".parse::<Integer>(), Err(super::ParseNumberError::InvalidDigit));
+        assert_eq!(Integer::from_str_radix("-ff", 16), Ok(Integer::from_i128(-255)));
+        assert_eq!(Integer::from_str_radix("102", 2), Err(super::ParseNumberError::InvalidDigit));
+    }
+
+    #[test]
+    fn bytes_test() {
+        let value: Integer = "-340282366920938463463374607431768211457".parse().unwrap();
+        let (sign, bytes) = value.to_bytes_le();
+        assert_eq!(sign, Sign::Negative);
+        assert_eq!(bytes, [vec![1u8], vec![0u8; 15], vec![1u8]].concat());
+        assert_eq!(Integer::from_bytes_le(sign, &bytes), value);
+        assert_eq!(Integer::from_i128(258).to_bytes_be(), (Sign::Positive, vec![1, 2]));
+        assert_eq!(Integer::from_bytes_be(Sign::Positive, &[1, 2]), Integer::from_i128(258));
+        assert_eq!(Integer::from_i128(0).to_bytes_le(), (Sign::Zero, vec![0]));
+        assert_eq!(value.to_u64_digits(), vec![1, 0, 1]);
+        assert_eq!(Integer::from_u64_digits(Sign::Negative, &[1, 0, 1]), value);
+    }
+
+    #[test]
+    fn signed_bytes_test() {
+        let cases: Vec<(i128, Vec<u8>)> = vec![
+            (0, vec![0]),
+            (127, vec![0x7f]),
+            (128, vec![0x80, 0]),
+            (-128, vec![0x80]),
+            (-129, vec![0x7f, 0xff]),
+            (-256, vec![0, 0xff]),
+            (-255, vec![1, 0xff]),
+        ];
+        for (value, bytes) in cases {
+            let value = Integer::from_i128(value);
+            assert_eq!(value.to_signed_bytes_le(), bytes);
+            assert_eq!(Integer::from_signed_bytes_le(&bytes), value);
+        }
+        let value: Integer = "-12345678901234567890123456789012345678901234567890".parse().unwrap();
+        assert_eq!(Integer::from_signed_bytes_be(&value.to_signed_bytes_be()), value);
+        assert_eq!(Integer::from_signed_bytes_le(&[]), Integer::from_i128(0));
+    }
+
     #[test]
     fn mul_test() {
         let a = Integer::from_number_slice(
-            &vec![0 as Digit, 340282366920938463463374607431768211454 as Digit],
+            &vec![0 as Digit, Digit::MAX - 1],
             Sign::Positive,
         );
-        let b = Integer::from_u128(1 as Digit);
+        let b = Integer::from_u128(1);
         assert_eq!(&a * &b, a);
         assert_eq!(&b * &a, a);
     }
 }
+
+#[cfg(test)]
+mod rational_test {
+    use super::{Integer, Rational};
+    use crate::math_traits::FromPrimitiveNumber;
+
+    fn rational(positive: i128, divider: i128) -> Rational {
+        return Rational::new(&Integer::from_i128(positive), &Integer::from_i128(divider));
+    }
+
+    #[test]
+    fn cmp_test() {
+        assert!(rational(1, -2) < rational(1, 3));
+        assert!(rational(-3, 4) < rational(-2, 3));
+        assert_eq!(rational(2, 4).cmp(&rational(1, -2)), std::cmp::Ordering::Greater);
+        assert_eq!(rational(2, 4).cmp(&rational(1, 2)), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn reduction_test() {
+        let value = Rational::new(&Integer::from_i128(4), &Integer::from_i128(-8));
+        assert_eq!(value, Rational::new(&Integer::from_i128(-1), &Integer::from_i128(2)));
+        assert_eq!(rational(0, -5), rational(0, 1));
+    }
+
+    #[test]
+    fn div_test() {
+        assert_eq!(rational(3, 4) / rational(-1, 2), rational(-3, 2));
+        let mut value = rational(3, 4);
+        value /= rational(-1, 2);
+        assert_eq!(value, rational(-3, 2));
+    }
+
+    #[test]
+    fn assign_test() {
+        let mut value = rational(1, 6);
+        value += rational(1, 3);
+        assert_eq!(value, rational(1, 2));
+        value -= rational(3, 4);
+        assert_eq!(value, rational(-1, 4));
+        value *= rational(-2, 3);
+        assert_eq!(value, rational(1, 6));
+    }
+
+    #[test]
+    fn string_test() {
+        assert_eq!(rational(6, -4).to_string(), "-3/2");
+        assert_eq!("6/-4".parse::<Rational>().unwrap(), rational(-3, 2));
+        assert_eq!("1/0".parse::<Rational>(), Err(super::ParseNumberError::DivideByZero));
+    }
+
+    #[test]
+    fn rem_test() {
+        let lhs: Rational = "-7/2".parse().unwrap();
+        let rhs: Rational = "4/3".parse().unwrap();
+        assert_eq!((&lhs % &rhs).to_string(), "-5/6");
+    }
+
+    #[test]
+    fn decimal_parse_test() {
+        assert_eq!("-1.25".parse::<Rational>().unwrap().to_string(), "-5/4");
+        assert_eq!("7".parse::<Rational>().unwrap().to_string(), "7");
+    }
+
+    #[test]
+    fn accessor_test() {
+        let value: Rational = "-6/4".parse().unwrap();
+        assert_eq!(value.numer(), &Integer::from_i128(-3));
+        assert_eq!(value.denom(), &Integer::from_i128(2));
+        assert_eq!(value.recip().to_string(), "-2/3");
+        assert_eq!(value.abs().to_string(), "3/2");
+        assert_eq!(value.signum().to_string(), "-1");
+        assert!(!value.is_integer());
+        assert!(Rational::from(4u8).is_integer());
+        assert_eq!(value.into_parts(), (Integer::from_i128(-3), Integer::from_i128(2)));
+    }
+
+    #[test]
+    fn to_integer_test() {
+        use super::RoundingMode::*;
+        let cases = [
+            ("5/2", [2, 3, 2, 3, 3, 2]),
+            ("-5/2", [-3, -2, -2, -3, -3, -2]),
+            ("7/2", [3, 4, 3, 4, 4, 4]),
+            ("-7/3", [-3, -2, -2, -3, -2, -2]),
+            ("8/3", [2, 3, 2, 3, 3, 3]),
+            ("-4", [-4, -4, -4, -4, -4, -4]),
+        ];
+        for (text, expected) in cases {
+            let value: Rational = text.parse().unwrap();
+            for (mode, expected) in [Floor, Ceiling, TowardZero, AwayFromZero, HalfAwayFromZero, HalfEven].iter().zip(expected) {
+                assert_eq!(value.to_integer(*mode), Integer::from(expected), "{} {:?}", text, mode);
+            }
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn conversion_test() {
+        let value = Integer::from(-12i64);
+        assert_eq!(Rational::from(&value), Rational::from_intager(&value));
+        assert_eq!(Rational::from(value), Rational::from(-12i8));
+        assert_eq!(Rational::from(u128::MAX).to_string(), u128::MAX.to_string());
+    }
+
+    #[test]
+    fn pow_test() {
+        let value: Rational = "-2/3".parse().unwrap();
+        assert_eq!(value.pow(3).to_string(), "-8/27");
+        assert_eq!(value.pow(-3).to_string(), "-27/8");
+        assert_eq!(value.pow(-2).to_string(), "9/4");
+        assert_eq!(value.pow(0).to_string(), "1");
+    }
+
+    #[test]
+    fn root_test() {
+        let value: Rational = "49/64".parse().unwrap();
+        assert_eq!(value.checked_sqrt(), Some("7/8".parse().unwrap()));
+        assert_eq!("1/2".parse::<Rational>().unwrap().checked_sqrt(), None);
+        assert_eq!("-4".parse::<Rational>().unwrap().checked_sqrt(), None);
+        assert_eq!("-8/125".parse::<Rational>().unwrap().nth_root_exact(3), Some("-2/5".parse().unwrap()));
+        assert_eq!("9/125".parse::<Rational>().unwrap().nth_root_exact(3), None);
+        assert_eq!("0".parse::<Rational>().unwrap().checked_sqrt(), Some("0".parse().unwrap()));
+    }
+}
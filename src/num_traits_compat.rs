@@ -0,0 +1,569 @@
+//! `num-traits`/`num-bigint`/`num-rational`との相互運用
+//!
+//! `num-traits`機能を有効にした場合のみコンパイルされます。
+//! rfm独自の`math_traits`とは名前が衝突するため、このモジュールでは`num_traits::`を付けて参照します。
+use num_bigint::{BigInt, BigUint};
+use num_rational::BigRational;
+
+use crate::math_traits::{FromPrimitiveNumber, One, Pow, Zero};
+use crate::num::{Integer, ParseNumberError, Rational, Sign};
+
+/// 浮動小数点数を厳密に有理数へ変換する(NaNと無限大はNone)
+fn f64_to_rational(value: f64) -> Option<Rational> {
+    if !value.is_finite() {
+        return None;
+    }
+    let bits = value.to_bits();
+    let negative = bits >> 63 == 1;
+    let exponent = ((bits >> 52) & 0x7ff) as i64;
+    let fraction = bits & ((1 << 52) - 1);
+    //非正規化数は暗黙の1を持たない
+    let (mantissa, exponent) = match exponent {
+        0 => (fraction, -1074),
+        _ => (fraction | (1 << 52), exponent - 1075),
+    };
+    let mantissa = match negative {
+        true => -Integer::from_u64(mantissa),
+        false => Integer::from_u64(mantissa),
+    };
+    if exponent >= 0 {
+        return Some(Rational::from(&(mantissa << exponent as usize)));
+    }
+    return Some(Rational::new(&mantissa, &(Integer::one() << (-exponent) as usize)));
+}
+
+/// 2^exponent倍する(途中でのオーバーフロー・アンダーフローを避けるため分割して掛ける)
+fn scale_by_power_of_two(mut value: f64, mut exponent: i64) -> f64 {
+    while exponent != 0 {
+        let step = exponent.clamp(-1000, 1000);
+        value *= 2f64.powi(step as i32);
+        exponent -= step;
+    }
+    return value;
+}
+
+/// 2^-1022未満の正の値p/qを非正規化数の刻み(2^-1074)で最近接偶数丸めする
+fn subnormal_to_f64(numerator: &Integer, divider: &Integer) -> f64 {
+    let (quotient, remainder) = crate::math_traits::DivRem::div_rem(&(numerator << 1074), divider);
+    //2^-1022未満なので商は2^52未満
+    let mut units = quotient.to_u64_digits().first().copied().unwrap_or(0);
+    let round_up = match (&remainder << 1).cmp(divider) {
+        std::cmp::Ordering::Greater => true,
+        std::cmp::Ordering::Equal => units & 1 == 1,
+        std::cmp::Ordering::Less => false,
+    };
+    units += round_up as u64;
+    //units·2^-1074 は表現可能なので、この倍率変更は誤差を生まない
+    return scale_by_power_of_two(units as f64, -1074);
+}
+
+/// 有理数を最も近い浮動小数点数に変換する
+fn rational_to_f64(value: &Rational) -> f64 {
+    if value.positive.sign() == Sign::Zero {
+        return 0.0;
+    }
+    let numerator = value.positive.abs();
+    //商が66bit以上になるよう分子を桁上げしてから割る
+    let shift = 66 - numerator.bit_length() as i64 + value.divider.bit_length() as i64;
+    let (quotient, remainder) = match shift {
+        0.. => crate::math_traits::DivRem::div_rem(&(&numerator << shift as usize), &value.divider),
+        _ => crate::math_traits::DivRem::div_rem(&numerator, &(&value.divider << (-shift) as usize)),
+    };
+    //2^exponent <= |value| < 2^(exponent + 1)
+    let exponent = quotient.bit_length() as i64 - 1 - shift;
+    let magnitude = match exponent < -1022 {
+        //非正規化数の範囲では丸め位置が仮数の53bit目より上に来るため、二重丸めを避けて直接丸める
+        true => subnormal_to_f64(&numerator, &value.divider),
+        false => {
+            //下位bitを丸め用の情報(sticky bit)として残して64bitに収める
+            let drop = quotient.bit_length() as i64 - 64;
+            let top = &quotient >> drop as usize;
+            let sticky = remainder.sign() != Sign::Zero || &top << drop as usize != quotient;
+            let top = top.to_u64_digits()[0] | sticky as u64;
+            scale_by_power_of_two(top as f64, drop - shift)
+        }
+    };
+    return match value.positive.sign() {
+        Sign::Negative => -magnitude,
+        _ => magnitude,
+    };
+}
+
+impl From<&BigInt> for Integer {
+    fn from(value: &BigInt) -> Self {
+        let (sign, bytes) = value.to_bytes_le();
+        let sign = match sign {
+            num_bigint::Sign::Minus => Sign::Negative,
+            num_bigint::Sign::NoSign => Sign::Zero,
+            num_bigint::Sign::Plus => Sign::Positive,
+        };
+        return Integer::from_bytes_le(sign, &bytes);
+    }
+}
+
+impl From<BigInt> for Integer {
+    fn from(value: BigInt) -> Self {
+        return Integer::from(&value);
+    }
+}
+
+impl From<&BigUint> for Integer {
+    fn from(value: &BigUint) -> Self {
+        return Integer::from_bytes_le(Sign::Positive, &value.to_bytes_le());
+    }
+}
+
+impl From<BigUint> for Integer {
+    fn from(value: BigUint) -> Self {
+        return Integer::from(&value);
+    }
+}
+
+impl From<&Integer> for BigInt {
+    fn from(value: &Integer) -> Self {
+        let (sign, bytes) = value.to_bytes_le();
+        let sign = match sign {
+            Sign::Negative => num_bigint::Sign::Minus,
+            Sign::Zero => num_bigint::Sign::NoSign,
+            Sign::Positive => num_bigint::Sign::Plus,
+        };
+        return BigInt::from_bytes_le(sign, &bytes);
+    }
+}
+
+impl From<Integer> for BigInt {
+    fn from(value: Integer) -> Self {
+        return BigInt::from(&value);
+    }
+}
+
+impl From<&BigRational> for Rational {
+    fn from(value: &BigRational) -> Self {
+        return Rational::new(&Integer::from(value.numer()), &Integer::from(value.denom()));
+    }
+}
+
+impl From<BigRational> for Rational {
+    fn from(value: BigRational) -> Self {
+        return Rational::from(&value);
+    }
+}
+
+impl From<&Rational> for BigRational {
+    fn from(value: &Rational) -> Self {
+        //Rationalは常に既約で分母が正なので約分は不要
+        return BigRational::new_raw(BigInt::from(&value.positive), BigInt::from(&value.divider));
+    }
+}
+
+impl From<Rational> for BigRational {
+    fn from(value: Rational) -> Self {
+        return BigRational::from(&value);
+    }
+}
+
+impl num_traits::Zero for Integer {
+    fn zero() -> Self {
+        return <Integer as Zero>::zero();
+    }
+
+    fn is_zero(&self) -> bool {
+        return self.sign() == Sign::Zero;
+    }
+}
+
+impl num_traits::One for Integer {
+    fn one() -> Self {
+        return <Integer as One>::one();
+    }
+}
+
+impl num_traits::Num for Integer {
+    type FromStrRadixErr = ParseNumberError;
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        return Integer::from_str_radix(s, radix);
+    }
+}
+
+impl num_traits::Signed for Integer {
+    fn abs(&self) -> Self {
+        return Integer::abs(self);
+    }
+
+    fn abs_sub(&self, other: &Self) -> Self {
+        if self <= other {
+            return <Integer as Zero>::zero();
+        }
+        return self - other;
+    }
+
+    fn signum(&self) -> Self {
+        return crate::math_traits::Signed::signum(self);
+    }
+
+    fn is_positive(&self) -> bool {
+        return self.sign() == Sign::Positive;
+    }
+
+    fn is_negative(&self) -> bool {
+        return self.sign() == Sign::Negative;
+    }
+}
+
+impl num_traits::Pow<u32> for &Integer {
+    type Output = Integer;
+    fn pow(self, rhs: u32) -> Self::Output {
+        return Pow::pow(self, Integer::from_u32(rhs));
+    }
+}
+
+impl num_traits::Pow<u32> for Integer {
+    type Output = Integer;
+    fn pow(self, rhs: u32) -> Self::Output {
+        return num_traits::Pow::pow(&self, rhs);
+    }
+}
+
+impl num_traits::Pow<u64> for &Integer {
+    type Output = Integer;
+    fn pow(self, rhs: u64) -> Self::Output {
+        return Pow::pow(self, Integer::from_u64(rhs));
+    }
+}
+
+impl num_traits::Pow<u64> for Integer {
+    type Output = Integer;
+    fn pow(self, rhs: u64) -> Self::Output {
+        return num_traits::Pow::pow(&self, rhs);
+    }
+}
+
+impl num_traits::FromPrimitive for Integer {
+    fn from_i64(n: i64) -> Option<Self> {
+        return Some(<Integer as FromPrimitiveNumber>::from_i64(n));
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        return Some(<Integer as FromPrimitiveNumber>::from_u64(n));
+    }
+
+    fn from_i128(n: i128) -> Option<Self> {
+        return Some(<Integer as FromPrimitiveNumber>::from_i128(n));
+    }
+
+    fn from_u128(n: u128) -> Option<Self> {
+        return Some(<Integer as FromPrimitiveNumber>::from_u128(n));
+    }
+
+    /// 小数部を切り捨てて変換する
+    fn from_f64(n: f64) -> Option<Self> {
+        let value = f64_to_rational(n.trunc())?;
+        return Some(value.positive);
+    }
+}
+
+impl num_traits::ToPrimitive for Integer {
+    fn to_i64(&self) -> Option<i64> {
+        return self.to_i128().and_then(|value| i64::try_from(value).ok());
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        return self.to_u128().and_then(|value| u64::try_from(value).ok());
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        return Integer::to_i128(self);
+    }
+
+    fn to_u128(&self) -> Option<u128> {
+        if self.sign() == Sign::Negative {
+            return None;
+        }
+        return self.magnitude_to_u128();
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        return Some(rational_to_f64(&Rational::from(self)));
+    }
+}
+
+impl num_traits::CheckedAdd for Integer {
+    fn checked_add(&self, v: &Self) -> Option<Self> {
+        return Some(self + v);
+    }
+}
+
+impl num_traits::CheckedSub for Integer {
+    fn checked_sub(&self, v: &Self) -> Option<Self> {
+        return Some(self - v);
+    }
+}
+
+impl num_traits::CheckedMul for Integer {
+    fn checked_mul(&self, v: &Self) -> Option<Self> {
+        return Some(self * v);
+    }
+}
+
+impl num_traits::CheckedDiv for Integer {
+    fn checked_div(&self, v: &Self) -> Option<Self> {
+        if v.sign() == Sign::Zero {
+            return None;
+        }
+        return Some(self / v);
+    }
+}
+
+impl num_traits::CheckedRem for Integer {
+    fn checked_rem(&self, v: &Self) -> Option<Self> {
+        if v.sign() == Sign::Zero {
+            return None;
+        }
+        return Some(self % v);
+    }
+}
+
+impl num_traits::CheckedNeg for Integer {
+    fn checked_neg(&self) -> Option<Self> {
+        return Some(-self);
+    }
+}
+
+impl num_traits::Zero for Rational {
+    fn zero() -> Self {
+        return <Rational as Zero>::zero();
+    }
+
+    fn is_zero(&self) -> bool {
+        return self.positive.sign() == Sign::Zero;
+    }
+}
+
+impl num_traits::One for Rational {
+    fn one() -> Self {
+        return <Rational as One>::one();
+    }
+}
+
+impl num_traits::Num for Rational {
+    type FromStrRadixErr = ParseNumberError;
+    /// `分子/分母`または整数の表記を読み込む
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        if let Some((positive, divider)) = s.split_once('/') {
+            let divider = Integer::from_str_radix(divider, radix)?;
+            if divider.sign() == Sign::Zero {
+                return Err(ParseNumberError::DivideByZero);
+            }
+            return Ok(Rational::new(&Integer::from_str_radix(positive, radix)?, &divider));
+        }
+        return Ok(Rational::from(&Integer::from_str_radix(s, radix)?));
+    }
+}
+
+impl num_traits::Signed for Rational {
+    fn abs(&self) -> Self {
+        return crate::math_traits::Signed::abs(self);
+    }
+
+    fn abs_sub(&self, other: &Self) -> Self {
+        if self <= other {
+            return <Rational as Zero>::zero();
+        }
+        return self - other;
+    }
+
+    fn signum(&self) -> Self {
+        return crate::math_traits::Signed::signum(self);
+    }
+
+    fn is_positive(&self) -> bool {
+        return self.positive.sign() == Sign::Positive;
+    }
+
+    fn is_negative(&self) -> bool {
+        return self.positive.sign() == Sign::Negative;
+    }
+}
+
+impl num_traits::Pow<i32> for &Rational {
+    type Output = Rational;
+    /// # Panics
+    /// 0を負の指数で累乗した場合
+    fn pow(self, rhs: i32) -> Self::Output {
+        return Rational::pow(self, rhs as i64);
+    }
+}
+
+impl num_traits::Pow<i32> for Rational {
+    type Output = Rational;
+    fn pow(self, rhs: i32) -> Self::Output {
+        return num_traits::Pow::pow(&self, rhs);
+    }
+}
+
+impl num_traits::FromPrimitive for Rational {
+    fn from_i64(n: i64) -> Option<Self> {
+        return Some(<Rational as FromPrimitiveNumber>::from_i64(n));
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        return Some(<Rational as FromPrimitiveNumber>::from_u64(n));
+    }
+
+    fn from_i128(n: i128) -> Option<Self> {
+        return Some(<Rational as FromPrimitiveNumber>::from_i128(n));
+    }
+
+    fn from_u128(n: u128) -> Option<Self> {
+        return Some(<Rational as FromPrimitiveNumber>::from_u128(n));
+    }
+
+    /// 浮動小数点数の値をそのまま(誤差なく)変換する
+    fn from_f64(n: f64) -> Option<Self> {
+        return f64_to_rational(n);
+    }
+}
+
+impl num_traits::ToPrimitive for Rational {
+    /// 小数部を切り捨てて変換する
+    fn to_i64(&self) -> Option<i64> {
+        return num_traits::ToPrimitive::to_i64(&(&self.positive / &self.divider));
+    }
+
+    /// 小数部を切り捨てて変換する
+    fn to_u64(&self) -> Option<u64> {
+        return num_traits::ToPrimitive::to_u64(&(&self.positive / &self.divider));
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        return num_traits::ToPrimitive::to_i128(&(&self.positive / &self.divider));
+    }
+
+    fn to_u128(&self) -> Option<u128> {
+        return num_traits::ToPrimitive::to_u128(&(&self.positive / &self.divider));
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        return Some(rational_to_f64(self));
+    }
+}
+
+impl num_traits::CheckedAdd for Rational {
+    fn checked_add(&self, v: &Self) -> Option<Self> {
+        return Some(self + v);
+    }
+}
+
+impl num_traits::CheckedSub for Rational {
+    fn checked_sub(&self, v: &Self) -> Option<Self> {
+        return Some(self - v);
+    }
+}
+
+impl num_traits::CheckedMul for Rational {
+    fn checked_mul(&self, v: &Self) -> Option<Self> {
+        return Some(self * v);
+    }
+}
+
+impl num_traits::CheckedDiv for Rational {
+    fn checked_div(&self, v: &Self) -> Option<Self> {
+        if v.positive.sign() == Sign::Zero {
+            return None;
+        }
+        return Some(self / v);
+    }
+}
+
+impl num_traits::CheckedRem for Rational {
+    fn checked_rem(&self, v: &Self) -> Option<Self> {
+        if v.positive.sign() == Sign::Zero {
+            return None;
+        }
+        return Some(self % v);
+    }
+}
+
+impl num_traits::CheckedNeg for Rational {
+    fn checked_neg(&self) -> Option<Self> {
+        return Some(-self);
+    }
+}
+
+#[cfg(test)]
+mod num_traits_compat_test {
+    use num_bigint::BigInt;
+    use num_rational::BigRational;
+    use num_traits::{CheckedDiv, Num, Pow, Signed, ToPrimitive, Zero};
+
+    use crate::math_traits::FromPrimitiveNumber;
+    use crate::num::{Integer, Rational};
+
+    ///num-traitsの境界で書かれた汎用関数の例
+    fn sum_of_abs<T: Num + Signed + Clone>(values: &[T]) -> T {
+        return values.iter().fold(T::zero(), |acc, value| acc + value.abs());
+    }
+
+    #[test]
+    fn generic_test() {
+        let values = vec![Integer::from_i128(-3), Integer::from_i128(4)];
+        assert_eq!(sum_of_abs(&values), Integer::from_i128(7));
+        let values: Vec<Rational> = vec!["-1/2".parse().unwrap(), "1/3".parse().unwrap()];
+        assert_eq!(sum_of_abs(&values), "5/6".parse().unwrap());
+        assert!(<Integer as Zero>::is_zero(&Integer::from_i128(0)));
+        assert_eq!(Integer::from_i128(3).pow(4u32), Integer::from_i128(81));
+        assert_eq!("2/3".parse::<Rational>().unwrap().pow(-2), "9/4".parse().unwrap());
+        assert_eq!(Integer::from_i128(1).checked_div(&Integer::from_i128(0)), None);
+        assert_eq!(<Rational as Num>::from_str_radix("-a/c", 16).unwrap(), "-5/6".parse().unwrap());
+    }
+
+    #[test]
+    fn primitive_test() {
+        let big: Integer = "-123456789012345678901234567890123456789012345".parse().unwrap();
+        assert_eq!(big.to_i64(), None);
+        assert_eq!(Integer::from_i128(-42).to_i64(), Some(-42));
+        assert_eq!(Integer::from_i128(-42).to_u64(), None);
+        assert_eq!(big.to_f64(), Some(-1.2345678901234567e44));
+        assert_eq!("1/3".parse::<Rational>().unwrap().to_f64(), Some(1.0 / 3.0));
+        assert_eq!("-7/2".parse::<Rational>().unwrap().to_i64(), Some(-3));
+        assert_eq!(<Rational as num_traits::FromPrimitive>::from_f64(0.375), Some("3/8".parse().unwrap()));
+        assert_eq!(<Rational as num_traits::FromPrimitive>::from_f64(f64::NAN), None);
+        assert_eq!(<Integer as num_traits::FromPrimitive>::from_f64(-2.5e20), Some("-250000000000000000000".parse().unwrap()));
+    }
+
+    #[test]
+    fn subnormal_test() {
+        let scaled = |numerator: &str, exponent: usize| -> Rational {
+            return Rational::new(&numerator.parse().unwrap(), &(Integer::from_i128(1) << exponent));
+        };
+        //2^-1075 + 2^-1136 は最小の非正規化数の半分をわずかに超える
+        assert_eq!(scaled("2305843009213693953", 1136).to_f64(), Some(5e-324));
+        assert_eq!((-scaled("2305843009213693953", 1136)).to_f64(), Some(-5e-324));
+        //ちょうど中間の値は偶数側に丸める
+        assert_eq!(scaled("1", 1075).to_f64(), Some(0.0));
+        assert_eq!(scaled("3", 1075).to_f64(), Some(1e-323));
+        assert_eq!(scaled("3", 1076).to_f64(), Some(5e-324));
+        //最大の非正規化数と最小の正規化数の境界
+        assert_eq!(scaled("9007199254740991", 1075).to_f64(), Some(f64::MIN_POSITIVE));
+        assert_eq!(scaled("9007199254740989", 1075).to_f64(), Some(f64::MIN_POSITIVE - 1e-323));
+        for value in [5e-324, 1.2345e-310, f64::MIN_POSITIVE - 5e-324, f64::MIN_POSITIVE, -2.5e-320] {
+            let exact = <Rational as num_traits::FromPrimitive>::from_f64(value).unwrap();
+            assert_eq!(exact.to_f64(), Some(value));
+        }
+    }
+
+    #[test]
+    fn conversion_test() {
+        let text = "-340282366920938463463374607431768211457123";
+        let value: Integer = text.parse().unwrap();
+        let big = BigInt::from(&value);
+        assert_eq!(big.to_string(), text);
+        assert_eq!(Integer::from(big), value);
+        assert_eq!(Integer::from(BigInt::from(0)), Integer::from_i128(0));
+
+        let ratio: Rational = "22/-7".parse().unwrap();
+        let big = BigRational::from(&ratio);
+        assert_eq!(big.to_string(), "-22/7");
+        assert_eq!(Rational::from(big), ratio);
+    }
+}
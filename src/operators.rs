@@ -0,0 +1,291 @@
+//! 演算子の組み合わせを補うモジュール
+//!
+//! `num`で定義した`&T op &T`の演算を元に、所有権の組み合わせ、プリミティブ整数との演算、
+//! `Integer`と`Rational`の間の演算(結果は`Rational`に昇格する)、各種`*Assign`、`Sum`/`Product`を実装します。
+use std::iter::{Product, Sum};
+use std::ops::*;
+
+use crate::math_traits::{FromPrimitiveNumber, One, Zero};
+use crate::num::{Integer, Rational};
+
+/// 所有権を持つ側の組み合わせを`&L op &R`の実装に委譲する
+macro_rules! forward_ref_binop {
+    ($imp:ident, $method:ident, $lhs:ty, $rhs:ty, $out:ty) => {
+        impl $imp<$rhs> for &$lhs {
+            type Output = $out;
+            fn $method(self, rhs: $rhs) -> $out {
+                return $imp::$method(self, &rhs);
+            }
+        }
+
+        impl $imp<&$rhs> for $lhs {
+            type Output = $out;
+            fn $method(self, rhs: &$rhs) -> $out {
+                return $imp::$method(&self, rhs);
+            }
+        }
+    };
+}
+
+/// `forward_ref_binop`に加えて`L op R`も委譲する
+macro_rules! forward_all_binop {
+    ($imp:ident, $method:ident, $lhs:ty, $rhs:ty, $out:ty) => {
+        forward_ref_binop!($imp, $method, $lhs, $rhs, $out);
+
+        impl $imp<$rhs> for $lhs {
+            type Output = $out;
+            fn $method(self, rhs: $rhs) -> $out {
+                return $imp::$method(&self, &rhs);
+            }
+        }
+    };
+}
+
+/// 参照を右辺にとる`*Assign`を`&T op &R`の実装に委譲する
+macro_rules! forward_assign {
+    ($imp:ident, $method:ident, $op:ident, $op_method:ident, $lhs:ty, $rhs:ty) => {
+        impl $imp<&$rhs> for $lhs {
+            fn $method(&mut self, rhs: &$rhs) {
+                *self = $op::$op_method(&*self, rhs);
+            }
+        }
+    };
+}
+
+/// 所有権を持つ右辺をとる`*Assign`も委譲する
+macro_rules! forward_all_assign {
+    ($imp:ident, $method:ident, $op:ident, $op_method:ident, $lhs:ty, $rhs:ty) => {
+        forward_assign!($imp, $method, $op, $op_method, $lhs, $rhs);
+
+        impl $imp<$rhs> for $lhs {
+            fn $method(&mut self, rhs: $rhs) {
+                *self = $op::$op_method(&*self, &rhs);
+            }
+        }
+    };
+}
+
+//同じ型同士: &T op T と T op &T
+forward_ref_binop!(Add, add, Integer, Integer, Integer);
+forward_ref_binop!(Sub, sub, Integer, Integer, Integer);
+forward_ref_binop!(Mul, mul, Integer, Integer, Integer);
+forward_ref_binop!(Div, div, Integer, Integer, Integer);
+forward_ref_binop!(Rem, rem, Integer, Integer, Integer);
+forward_ref_binop!(Add, add, Rational, Rational, Rational);
+forward_ref_binop!(Sub, sub, Rational, Rational, Rational);
+forward_ref_binop!(Mul, mul, Rational, Rational, Rational);
+forward_ref_binop!(Div, div, Rational, Rational, Rational);
+forward_ref_binop!(Rem, rem, Rational, Rational, Rational);
+
+//同じ型同士の*Assign
+forward_all_assign!(DivAssign, div_assign, Div, div, Integer, Integer);
+forward_all_assign!(RemAssign, rem_assign, Rem, rem, Integer, Integer);
+forward_assign!(AddAssign, add_assign, Add, add, Rational, Rational);
+forward_assign!(SubAssign, sub_assign, Sub, sub, Rational, Rational);
+forward_assign!(MulAssign, mul_assign, Mul, mul, Rational, Rational);
+forward_assign!(DivAssign, div_assign, Div, div, Rational, Rational);
+forward_assign!(RemAssign, rem_assign, Rem, rem, Rational, Rational);
+
+/// IntegerとRationalの演算(Integerを分母1の有理数に昇格させる)
+macro_rules! integer_rational_binop {
+    ($imp:ident, $method:ident, $assign:ident, $assign_method:ident) => {
+        impl $imp<&Rational> for &Integer {
+            type Output = Rational;
+            fn $method(self, rhs: &Rational) -> Rational {
+                return $imp::$method(&Rational::from(self), rhs);
+            }
+        }
+
+        impl $imp<&Integer> for &Rational {
+            type Output = Rational;
+            fn $method(self, rhs: &Integer) -> Rational {
+                return $imp::$method(self, &Rational::from(rhs));
+            }
+        }
+
+        forward_all_binop!($imp, $method, Integer, Rational, Rational);
+        forward_all_binop!($imp, $method, Rational, Integer, Rational);
+        forward_all_assign!($assign, $assign_method, $imp, $method, Rational, Integer);
+    };
+}
+
+integer_rational_binop!(Add, add, AddAssign, add_assign);
+integer_rational_binop!(Sub, sub, SubAssign, sub_assign);
+integer_rational_binop!(Mul, mul, MulAssign, mul_assign);
+integer_rational_binop!(Div, div, DivAssign, div_assign);
+integer_rational_binop!(Rem, rem, RemAssign, rem_assign);
+
+/// プリミティブ整数との演算(プリミティブ側を変換してから計算する)
+/// 変換は`i128`/`u128`を経由するため、同じ型へのキャストに対する警告は抑制する
+macro_rules! primitive_binop {
+    ($imp:ident, $method:ident, $assign:ident, $assign_method:ident, $target:ty, $convert:expr, $($primitive:ty),*) => {$(
+        #[allow(clippy::unnecessary_cast)]
+        impl $imp<$primitive> for &$target {
+            type Output = $target;
+            fn $method(self, rhs: $primitive) -> $target {
+                return $imp::$method(self, &($convert)(rhs));
+            }
+        }
+
+        #[allow(clippy::unnecessary_cast)]
+        impl $imp<$primitive> for $target {
+            type Output = $target;
+            fn $method(self, rhs: $primitive) -> $target {
+                return $imp::$method(&self, &($convert)(rhs));
+            }
+        }
+
+        #[allow(clippy::unnecessary_cast)]
+        impl $imp<&$target> for $primitive {
+            type Output = $target;
+            fn $method(self, rhs: &$target) -> $target {
+                return $imp::$method(&($convert)(self), rhs);
+            }
+        }
+
+        #[allow(clippy::unnecessary_cast)]
+        impl $imp<$target> for $primitive {
+            type Output = $target;
+            fn $method(self, rhs: $target) -> $target {
+                return $imp::$method(&($convert)(self), &rhs);
+            }
+        }
+
+        #[allow(clippy::unnecessary_cast)]
+        impl $assign<$primitive> for $target {
+            fn $assign_method(&mut self, rhs: $primitive) {
+                $assign::$assign_method(self, &($convert)(rhs));
+            }
+        }
+    )*};
+}
+
+/// 四則演算と剰余の全てについてプリミティブ整数との演算を実装する
+macro_rules! primitive_ops {
+    ($target:ty, $convert:expr, $($primitive:ty),*) => {
+        primitive_binop!(Add, add, AddAssign, add_assign, $target, $convert, $($primitive),*);
+        primitive_binop!(Sub, sub, SubAssign, sub_assign, $target, $convert, $($primitive),*);
+        primitive_binop!(Mul, mul, MulAssign, mul_assign, $target, $convert, $($primitive),*);
+        primitive_binop!(Div, div, DivAssign, div_assign, $target, $convert, $($primitive),*);
+        primitive_binop!(Rem, rem, RemAssign, rem_assign, $target, $convert, $($primitive),*);
+    };
+}
+
+primitive_ops!(Integer, |value| Integer::from_i128(value as i128), i8, i16, i32, i64, i128, isize);
+primitive_ops!(Integer, |value| Integer::from_u128(value as u128), u8, u16, u32, u64, u128, usize);
+primitive_ops!(Rational, |value| Rational::from_i128(value as i128), i8, i16, i32, i64, i128, isize);
+primitive_ops!(Rational, |value| Rational::from_u128(value as u128), u8, u16, u32, u64, u128, usize);
+
+/// 総和と総乗(累積値はその場で更新する)
+macro_rules! sum_product {
+    ($target:ty) => {
+        impl Sum for $target {
+            fn sum<I: Iterator<Item = $target>>(iter: I) -> Self {
+                return iter.fold(<$target>::zero(), |mut acc, value| {
+                    acc += &value;
+                    acc
+                });
+            }
+        }
+
+        impl<'a> Sum<&'a $target> for $target {
+            fn sum<I: Iterator<Item = &'a $target>>(iter: I) -> Self {
+                return iter.fold(<$target>::zero(), |mut acc, value| {
+                    acc += value;
+                    acc
+                });
+            }
+        }
+
+        impl Product for $target {
+            fn product<I: Iterator<Item = $target>>(iter: I) -> Self {
+                return iter.fold(<$target>::one(), |mut acc, value| {
+                    acc *= &value;
+                    acc
+                });
+            }
+        }
+
+        impl<'a> Product<&'a $target> for $target {
+            fn product<I: Iterator<Item = &'a $target>>(iter: I) -> Self {
+                return iter.fold(<$target>::one(), |mut acc, value| {
+                    acc *= value;
+                    acc
+                });
+            }
+        }
+    };
+}
+
+sum_product!(Integer);
+sum_product!(Rational);
+
+#[cfg(test)]
+mod operators_test {
+    use crate::math_traits::FromPrimitiveNumber;
+    use crate::num::{Integer, Rational};
+
+    fn rational(s: &str) -> Rational {
+        return s.parse().unwrap();
+    }
+
+    #[test]
+    fn ownership_test() {
+        let a = Integer::from_i128(7);
+        let b = Integer::from_i128(-2);
+        assert_eq!(&a + b.clone(), Integer::from_i128(5));
+        assert_eq!(a.clone() - &b, Integer::from_i128(9));
+        assert_eq!(&a / b.clone(), Integer::from_i128(-3));
+        assert_eq!(a.clone() % &b, Integer::from_i128(1));
+        let mut c = a.clone();
+        c /= &b;
+        assert_eq!(c, Integer::from_i128(-3));
+        c %= Integer::from_i128(2);
+        assert_eq!(c, Integer::from_i128(-1));
+    }
+
+    #[test]
+    fn primitive_test() {
+        let a = Integer::from_i128(10);
+        assert_eq!(&a + 1, Integer::from_i128(11));
+        assert_eq!(a.clone() * -3i64, Integer::from_i128(-30));
+        assert_eq!(100u8 - &a, Integer::from_i128(90));
+        assert_eq!(25 / a.clone(), Integer::from_i128(2));
+        let mut b = a.clone();
+        b += 5u32;
+        b %= 4;
+        assert_eq!(b, Integer::from_i128(3));
+
+        let half = rational("1/2");
+        assert_eq!(&half + 1, rational("3/2"));
+        assert_eq!(1 / half.clone(), rational("2"));
+        let mut c = half.clone();
+        c *= -4i8;
+        assert_eq!(c, rational("-2"));
+    }
+
+    #[test]
+    fn promotion_test() {
+        let a = Integer::from_i128(3);
+        let half = rational("1/2");
+        assert_eq!(&a + &half, rational("7/2"));
+        assert_eq!(half.clone() - a.clone(), rational("-5/2"));
+        assert_eq!(&a / half.clone(), rational("6"));
+        assert_eq!(rational("7/2") % &a, rational("1/2"));
+        let mut b = half.clone();
+        b += &a;
+        b *= a;
+        assert_eq!(b, rational("21/2"));
+    }
+
+    #[test]
+    fn sum_product_test() {
+        let values: Vec<Integer> = (1..=5).map(Integer::from_i128).collect();
+        assert_eq!(values.iter().sum::<Integer>(), Integer::from_i128(15));
+        assert_eq!(values.into_iter().product::<Integer>(), Integer::from_i128(120));
+        let values = vec![rational("1/2"), rational("1/3"), rational("1/6")];
+        assert_eq!(values.iter().sum::<Rational>(), rational("1"));
+        assert_eq!(values.into_iter().product::<Rational>(), rational("1/36"));
+        assert_eq!(Vec::<Integer>::new().into_iter().sum::<Integer>(), Integer::from_i128(0));
+    }
+}
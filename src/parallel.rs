@@ -0,0 +1,112 @@
+//! 巨大な数の演算を並列化するための設定
+//!
+//! `rayon`機能を有効にした場合、しきい値以上の大きさの
+//! Karatsuba法の部分積とbinary splitting法の分割を並列に計算します。
+//! 計算の分け方は逐次実行と同じため、結果は常に一致します。
+//! `rayon`機能が無効の場合、しきい値は設定できますが常に逐次実行となります。
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// 乗算を並列化する桁数の既定値
+pub const DEFAULT_MUL_THRESHOLD: usize = 2048;
+
+/// binary splitting法を並列化する項数の既定値
+pub const DEFAULT_SPLITTING_THRESHOLD: u64 = 512;
+
+static MUL_THRESHOLD: AtomicUsize = AtomicUsize::new(DEFAULT_MUL_THRESHOLD);
+static SPLITTING_THRESHOLD: AtomicU64 = AtomicU64::new(DEFAULT_SPLITTING_THRESHOLD);
+
+/// 乗算を並列化する桁数(Karatsuba法で分割する前の桁数)を設定する
+pub fn set_mul_threshold(digits: usize) {
+    MUL_THRESHOLD.store(digits, Ordering::Relaxed);
+}
+
+/// 乗算を並列化する桁数を取得する
+pub fn mul_threshold() -> usize {
+    return MUL_THRESHOLD.load(Ordering::Relaxed);
+}
+
+/// binary splitting法を並列化する項数を設定する
+pub fn set_splitting_threshold(terms: u64) {
+    SPLITTING_THRESHOLD.store(terms, Ordering::Relaxed);
+}
+
+/// binary splitting法を並列化する項数を取得する
+pub fn splitting_threshold() -> u64 {
+    return SPLITTING_THRESHOLD.load(Ordering::Relaxed);
+}
+
+/// `rayon`機能が有効な場合のみ`Send`を要求するトレイト
+#[cfg(feature = "rayon")]
+pub trait MaybeSend: Send {}
+#[cfg(feature = "rayon")]
+impl<T: Send + ?Sized> MaybeSend for T {}
+
+/// `rayon`機能が有効な場合のみ`Send`を要求するトレイト
+#[cfg(not(feature = "rayon"))]
+pub trait MaybeSend {}
+#[cfg(not(feature = "rayon"))]
+impl<T: ?Sized> MaybeSend for T {}
+
+/// `rayon`機能が有効な場合のみ`Sync`を要求するトレイト
+#[cfg(feature = "rayon")]
+pub trait MaybeSync: Sync {}
+#[cfg(feature = "rayon")]
+impl<T: Sync + ?Sized> MaybeSync for T {}
+
+/// `rayon`機能が有効な場合のみ`Sync`を要求するトレイト
+#[cfg(not(feature = "rayon"))]
+pub trait MaybeSync {}
+#[cfg(not(feature = "rayon"))]
+impl<T: ?Sized> MaybeSync for T {}
+
+/// 並列化が有効か(`rayon`機能が有効か)
+pub(crate) const ENABLED: bool = cfg!(feature = "rayon");
+
+/// 2つの処理を実行する(`rayon`機能が有効なら並列に実行する)
+pub(crate) fn join<A, B, RA, RB>(a: A, b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA + MaybeSend,
+    B: FnOnce() -> RB + MaybeSend,
+    RA: MaybeSend,
+    RB: MaybeSend,
+{
+    #[cfg(feature = "rayon")]
+    return rayon::join(a, b);
+    #[cfg(not(feature = "rayon"))]
+    return (a(), b());
+}
+
+#[cfg(test)]
+mod parallel_test {
+    use super::*;
+    use crate::algorithm::binary_splitting;
+    use crate::math_traits::FromPrimitiveNumber;
+    use crate::num::{Integer, Sign};
+    use crate::test_util::XorShift;
+
+    #[test]
+    fn deterministic_test() {
+        let mut random = XorShift::new();
+        let lhs = Integer::from_number_slice(&random.digits(300), Sign::Positive);
+        let rhs = Integer::from_number_slice(&random.digits(257), Sign::Negative);
+        let splitting = || {
+            binary_splitting(
+                1,
+                2000,
+                &|n| Integer::from_u128(2 * n + 1),
+                &|n| Integer::from_u128(3 * n),
+                &|n| Integer::from_u128(n),
+            )
+        };
+
+        set_mul_threshold(usize::MAX);
+        set_splitting_threshold(u64::MAX);
+        let serial = (&lhs * &rhs, &lhs * &lhs, splitting());
+        set_mul_threshold(32);
+        set_splitting_threshold(8);
+        let parallel = (&lhs * &rhs, &lhs * &lhs, splitting());
+        set_mul_threshold(DEFAULT_MUL_THRESHOLD);
+        set_splitting_threshold(DEFAULT_SPLITTING_THRESHOLD);
+        assert_eq!(serial, parallel);
+    }
+}
@@ -0,0 +1,721 @@
+/**1変数多項式を扱うモジュールです
+
+係数の型として`Integer`と`Rational`を想定しています。
+四則演算・評価・微分は係数が環であれば使え、除算・GCD・無平方分解・終結式は
+係数が体(`Rational`)の場合はユークリッドの互除法、`Integer`の場合は部分終結式(subresultant)を用いて計算します。
+いずれも係数の演算のみで定義されるため、結果は厳密です。
+*/
+use std::fmt;
+use std::ops::*;
+
+use crate::math_traits::*;
+use crate::num::{Integer, Rational, Sign};
+
+/// 1変数多項式
+///
+/// 係数は次数の低い順に保持し、最高次の係数は常に0以外です(零多項式は係数を持ちません)。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial<T> {
+    coefficients: Vec<T>,
+}
+
+impl<T: Zero + PartialEq> Polynomial<T> {
+    /// 次数の低い順に並べた係数から多項式を作成する(末尾の0は取り除かれる)
+    pub fn new(coefficients: Vec<T>) -> Polynomial<T> {
+        let mut result = Polynomial { coefficients };
+        result.trim();
+        return result;
+    }
+
+    /// 零多項式
+    pub fn zero() -> Polynomial<T> {
+        return Polynomial { coefficients: Vec::new() };
+    }
+
+    /// 定数多項式
+    pub fn constant(value: T) -> Polynomial<T> {
+        return Polynomial::new(vec![value]);
+    }
+
+    /// 次数の低い順に並べた係数を取得する
+    pub fn coefficients(&self) -> &[T] {
+        return &self.coefficients;
+    }
+
+    /// 次数を取得する(零多項式の場合はNone)
+    pub fn degree(&self) -> Option<usize> {
+        return self.coefficients.len().checked_sub(1);
+    }
+
+    /// 最高次の係数を取得する(零多項式の場合はNone)
+    pub fn leading_coefficient(&self) -> Option<&T> {
+        return self.coefficients.last();
+    }
+
+    /// 零多項式であるか
+    pub fn is_zero(&self) -> bool {
+        return self.coefficients.is_empty();
+    }
+
+    fn trim(&mut self) {
+        while self.coefficients.last().is_some_and(|value| value.is_zero()) {
+            self.coefficients.pop();
+        }
+    }
+}
+
+impl<T> Polynomial<T>
+where
+    T: Ring,
+    for<'a> &'a T: Add<&'a T, Output = T> + Sub<&'a T, Output = T> + Mul<&'a T, Output = T> + Neg<Output = T>,
+{
+    /// 単項式 coefficient·x^degree
+    pub fn monomial(coefficient: T, degree: usize) -> Polynomial<T> {
+        let mut coefficients = vec![T::zero(); degree];
+        coefficients.push(coefficient);
+        return Polynomial::new(coefficients);
+    }
+
+    /// xに値を代入した結果をホーナー法で求める
+    pub fn evaluate(&self, x: &T) -> T {
+        let mut result = T::zero();
+        for coefficient in self.coefficients.iter().rev() {
+            result = &(&result * x) + coefficient;
+        }
+        return result;
+    }
+
+    /// 各係数に定数を掛ける
+    pub fn scale(&self, factor: &T) -> Polynomial<T> {
+        return Polynomial::new(self.coefficients.iter().map(|value| value * factor).collect());
+    }
+
+    /// 導関数を求める
+    pub fn derivative(&self) -> Polynomial<T>
+    where
+        T: FromPrimitiveNumber,
+    {
+        let coefficients = self.coefficients.iter().enumerate().skip(1);
+        return Polynomial::new(coefficients.map(|(i_, value)| value * &T::from_u64(i_ as u64)).collect());
+    }
+
+    /**
+    擬除算 lc(divisor)^(δ+1)·self = q·divisor + r を行う(δは次数の差)
+
+    係数が体でなくても割り切れる形にしてから除算します。
+    # Returns
+    (q, r)のタプル。rの次数はdivisorの次数未満
+    # Panics
+    divisorが零多項式の場合
+    */
+    pub fn pseudo_div_rem(&self, divisor: &Polynomial<T>) -> (Polynomial<T>, Polynomial<T>) {
+        let divisor_degree = divisor.degree().expect("Divide by zero");
+        let Some(degree) = self.degree().filter(|degree| *degree >= divisor_degree) else {
+            return (Polynomial::zero(), self.clone());
+        };
+        let lead = divisor.coefficients[divisor_degree].clone();
+        let mut remain = self.coefficients.clone();
+        let mut quotient = vec![T::zero(); degree - divisor_degree + 1];
+        for shift in (0..=degree - divisor_degree).rev() {
+            //remain = lead·remain - top·x^shift·divisor
+            let top = remain[shift + divisor_degree].clone();
+            for value in quotient.iter_mut().chain(remain.iter_mut()) {
+                *value = &*value * &lead;
+            }
+            quotient[shift] = &quotient[shift] + &top;
+            for (i_, value) in divisor.coefficients.iter().enumerate() {
+                remain[shift + i_] = &remain[shift + i_] - &(&top * value);
+            }
+            remain.pop();
+        }
+        return (Polynomial::new(quotient), Polynomial::new(remain));
+    }
+}
+
+impl<T> Polynomial<T>
+where
+    T: Field + FromPrimitiveNumber,
+    for<'a> &'a T: Add<&'a T, Output = T>
+        + Sub<&'a T, Output = T>
+        + Mul<&'a T, Output = T>
+        + Div<&'a T, Output = T>
+        + Neg<Output = T>,
+{
+    /**
+    余りつき除算 self = q·divisor + r
+    # Returns
+    (q, r)のタプル。rの次数はdivisorの次数未満
+    # Panics
+    divisorが零多項式の場合
+    */
+    pub fn div_rem(&self, divisor: &Polynomial<T>) -> (Polynomial<T>, Polynomial<T>) {
+        let divisor_degree = divisor.degree().expect("Divide by zero");
+        let Some(degree) = self.degree().filter(|degree| *degree >= divisor_degree) else {
+            return (Polynomial::zero(), self.clone());
+        };
+        let lead = &divisor.coefficients[divisor_degree];
+        let mut remain = self.coefficients.clone();
+        let mut quotient = vec![T::zero(); degree - divisor_degree + 1];
+        for shift in (0..=degree - divisor_degree).rev() {
+            let factor = &remain[shift + divisor_degree] / lead;
+            for (i_, value) in divisor.coefficients.iter().enumerate() {
+                remain[shift + i_] = &remain[shift + i_] - &(&factor * value);
+            }
+            quotient[shift] = factor;
+            remain.pop();
+        }
+        return (Polynomial::new(quotient), Polynomial::new(remain));
+    }
+
+    /// 最高次の係数で割り、モニック多項式にする(零多項式はそのまま)
+    pub fn monic(&self) -> Polynomial<T> {
+        return match self.leading_coefficient() {
+            Some(lead) => self.scale(&lead.recip()),
+            None => self.clone(),
+        };
+    }
+
+    /// 最大公約式をユークリッドの互除法で求める
+    /// # Returns
+    /// モニックな最大公約式(両方が零多項式の場合は零多項式)
+    pub fn gcd(&self, other: &Polynomial<T>) -> Polynomial<T> {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let remain = a.div_rem(&b).1;
+            a = std::mem::replace(&mut b, remain);
+        }
+        return a.monic();
+    }
+
+    /**
+    無平方分解をYunのアルゴリズムで求める
+
+    self = c·f_1^1·f_2^2···f_k^k (f_iは互いに素で重根を持たないモニック多項式)と分解します。
+    # Returns
+    (c, [(f_i, i)])のタプル。cは最高次の係数で、f_iが定数となる組は含まない
+    */
+    pub fn square_free_factorization(&self) -> (T, Vec<(Polynomial<T>, u32)>) {
+        let Some(lead) = self.leading_coefficient() else {
+            return (T::zero(), Vec::new());
+        };
+        let mut factors = Vec::new();
+        let f = self.monic();
+        let derivative = f.derivative();
+        let common = f.gcd(&derivative);
+        let mut b = f.div_rem(&common).0;
+        let mut c = derivative.div_rem(&common).0;
+        let mut d = &c - &b.derivative();
+        let mut multiplicity = 1;
+        while b.degree() != Some(0) {
+            let factor = b.gcd(&d);
+            b = b.div_rem(&factor).0;
+            c = d.div_rem(&factor).0;
+            d = &c - &b.derivative();
+            if factor.degree() != Some(0) {
+                factors.push((factor, multiplicity));
+            }
+            multiplicity += 1;
+        }
+        return (lead.clone(), factors);
+    }
+
+    /// 終結式(シルベスター行列の行列式)をユークリッドの互除法で求める
+    /// いずれかが零多項式の場合は0
+    pub fn resultant(&self, other: &Polynomial<T>) -> T {
+        if self.is_zero() || other.is_zero() {
+            return T::zero();
+        }
+        let (mut a, mut b) = (self.clone(), other.clone());
+        let mut result = T::one();
+        loop {
+            let (m, n) = (a.coefficients.len() - 1, b.coefficients.len() - 1);
+            let lead = b.coefficients[n].clone();
+            if n == 0 {
+                return &result * &power::<T>(&lead, m);
+            }
+            //res(a, b) = (-1)^(mn)·lc(b)^(m-k)·res(b, a mod b) (kはa mod bの次数)
+            let remain = a.div_rem(&b).1;
+            let Some(k) = remain.degree() else {
+                return T::zero();
+            };
+            if m * n % 2 == 1 {
+                result = -&result;
+            }
+            result = &result * &power::<T>(&lead, m - k);
+            (a, b) = (b, remain);
+        }
+    }
+
+    /**
+    判別式 (-1)^(n(n-1)/2)·res(f, f')/lc(f) を求める(nは次数)
+
+    重根を持つ場合に限り0となります。
+    # Panics
+    次数が1未満の場合
+    */
+    pub fn discriminant(&self) -> T {
+        let degree = self.degree().filter(|degree| *degree >= 1).expect("degree must be at least 1");
+        let value = &self.resultant(&self.derivative()) / &self.coefficients[degree];
+        return match degree * (degree - 1) / 2 % 2 {
+            0 => value,
+            _ => -&value,
+        };
+    }
+}
+
+/// 繰り返し2乗法による累乗
+fn power<T>(base: &T, exp: usize) -> T
+where
+    T: One,
+    for<'a> &'a T: Mul<&'a T, Output = T>,
+{
+    let mut result = T::one();
+    for i_ in (0..usize::BITS - exp.leading_zeros()).rev() {
+        result = &result * &result;
+        if (exp >> i_) & 1 == 1 {
+            result = &result * base;
+        }
+    }
+    return result;
+}
+
+impl Polynomial<Integer> {
+    /// 内容(係数の最大公約数)を求める(零多項式の場合は0)
+    pub fn content(&self) -> Integer {
+        let mut result = Integer::zero();
+        for value in &self.coefficients {
+            result = crate::algorithm::gcd(&result, value);
+        }
+        return result;
+    }
+
+    /// 原始的部分(内容で割り、最高次の係数を正にした多項式)を求める
+    pub fn primitive_part(&self) -> Polynomial<Integer> {
+        let Some(lead) = self.leading_coefficient() else {
+            return Polynomial::zero();
+        };
+        let mut content = self.content();
+        if lead.sign() == Sign::Negative {
+            content = -content;
+        }
+        return self.exact_div(&content);
+    }
+
+    /// 各係数を割り切れる整数で割る
+    fn exact_div(&self, divisor: &Integer) -> Polynomial<Integer> {
+        return Polynomial::new(self.coefficients.iter().map(|value| value / divisor).collect());
+    }
+
+    /**
+    最大公約式を部分終結式(subresultant)剰余列で求める
+
+    係数の膨張を抑えながら整数係数のまま計算します。
+    # Returns
+    最高次の係数が正の最大公約式(両方が零多項式の場合は零多項式)
+    */
+    pub fn gcd(&self, other: &Polynomial<Integer>) -> Polynomial<Integer> {
+        let (mut a, mut b) = match self.degree() >= other.degree() {
+            true => (self.clone(), other.clone()),
+            false => (other.clone(), self.clone()),
+        };
+        if b.is_zero() {
+            return a.primitive_part().scale(&a.content());
+        }
+        let content = crate::algorithm::gcd(&a.content(), &b.content());
+        (a, b) = (a.primitive_part(), b.primitive_part());
+        let (mut g, mut h) = (Integer::one(), Integer::one());
+        loop {
+            let delta = a.coefficients.len() - b.coefficients.len();
+            let remain = a.pseudo_div_rem(&b).1;
+            match remain.degree() {
+                None => return b.primitive_part().scale(&content),
+                Some(0) => return Polynomial::constant(content),
+                _ => {}
+            }
+            a = b;
+            b = remain.exact_div(&(&g * &power::<Integer>(&h, delta)));
+            g = a.coefficients[a.coefficients.len() - 1].clone();
+            //h = g^δ / h^(δ-1)
+            h = match delta {
+                0 => h,
+                _ => &power::<Integer>(&g, delta) / &power::<Integer>(&h, delta - 1),
+            };
+        }
+    }
+
+    /// 終結式を部分終結式剰余列で求める(いずれかが零多項式の場合は0)
+    pub fn resultant(&self, other: &Polynomial<Integer>) -> Integer {
+        if self.is_zero() || other.is_zero() {
+            return Integer::zero();
+        }
+        let (mut a, mut b) = (self.clone(), other.clone());
+        let (content_a, content_b) = (a.content(), b.content());
+        a = a.exact_div(&content_a);
+        b = b.exact_div(&content_b);
+        let mut scale = &power::<Integer>(&content_a, b.coefficients.len() - 1) * &power::<Integer>(&content_b, a.coefficients.len() - 1);
+        let mut negate = false;
+        if a.coefficients.len() < b.coefficients.len() {
+            (a, b) = (b, a);
+            negate = (a.coefficients.len() - 1) % 2 == 1 && (b.coefficients.len() - 1) % 2 == 1;
+        }
+        let (mut g, mut h) = (Integer::one(), Integer::one());
+        while b.coefficients.len() > 1 {
+            let (m, n) = (a.coefficients.len() - 1, b.coefficients.len() - 1);
+            let delta = m - n;
+            if m % 2 == 1 && n % 2 == 1 {
+                negate = !negate;
+            }
+            let remain = a.pseudo_div_rem(&b).1;
+            a = b;
+            b = remain.exact_div(&(&g * &power::<Integer>(&h, delta)));
+            g = a.coefficients[n].clone();
+            //h = g^δ / h^(δ-1)
+            h = match delta {
+                0 => h,
+                _ => &power::<Integer>(&g, delta) / &power::<Integer>(&h, delta - 1),
+            };
+        }
+        //bが定数(または0)になったら終了
+        let last = b.coefficients.first().cloned().unwrap_or_else(Integer::zero);
+        let m = a.coefficients.len() - 1;
+        let result = match m {
+            0 => Integer::one(),
+            _ => &power::<Integer>(&last, m) / &power::<Integer>(&h, m - 1),
+        };
+        scale = &scale * &result;
+        return match negate {
+            true => -scale,
+            false => scale,
+        };
+    }
+
+    /**
+    判別式 (-1)^(n(n-1)/2)·res(f, f')/lc(f) を求める(nは次数)
+
+    重根を持つ場合に限り0となります。
+    # Panics
+    次数が1未満の場合
+    */
+    pub fn discriminant(&self) -> Integer {
+        let degree = self.degree().filter(|degree| *degree >= 1).expect("degree must be at least 1");
+        let value = &self.resultant(&self.derivative()) / &self.coefficients[degree];
+        return match degree * (degree - 1) / 2 % 2 {
+            0 => value,
+            _ => -value,
+        };
+    }
+
+    /**
+    無平方分解を求める
+
+    self = c·f_1^1·f_2^2···f_k^k (f_iは互いに素で重根を持たず、最高次の係数が正の原始多項式)と分解します。
+    # Returns
+    (c, [(f_i, i)])のタプル。cは符号付きの内容で、f_iが定数となる組は含まない
+    */
+    pub fn square_free_factorization(&self) -> (Integer, Vec<(Polynomial<Integer>, u32)>) {
+        let Some(lead) = self.leading_coefficient() else {
+            return (Integer::zero(), Vec::new());
+        };
+        let (_, factors) = Polynomial::<Rational>::from(self).square_free_factorization();
+        let factors: Vec<(Polynomial<Integer>, u32)> = factors
+            .iter()
+            .map(|(factor, multiplicity)| (factor.primitive_integer_part(), *multiplicity))
+            .collect();
+        //ガウスの補題より原始多項式の積は原始的なので、残りは定数
+        let mut product = Integer::one();
+        for (factor, multiplicity) in &factors {
+            product = &product * &power::<Integer>(factor.leading_coefficient().unwrap(), *multiplicity as usize);
+        }
+        return (lead / &product, factors);
+    }
+}
+
+impl Polynomial<Rational> {
+    /// 分母を払って得られる整数係数の原始多項式(最高次の係数は正)を求める
+    pub fn primitive_integer_part(&self) -> Polynomial<Integer> {
+        let mut denominator = Integer::one();
+        for value in &self.coefficients {
+            denominator = &(&denominator / &crate::algorithm::gcd(&denominator, value.denom())) * value.denom();
+        }
+        let scaled = self.coefficients.iter().map(|value| &(value.numer() * &denominator) / value.denom());
+        return Polynomial::new(scaled.collect()).primitive_part();
+    }
+}
+
+impl From<&Polynomial<Integer>> for Polynomial<Rational> {
+    fn from(value: &Polynomial<Integer>) -> Self {
+        return Polynomial {
+            coefficients: value.coefficients.iter().map(Rational::from).collect(),
+        };
+    }
+}
+
+impl<T> Add<&Polynomial<T>> for &Polynomial<T>
+where
+    T: Zero + PartialEq + Clone,
+    for<'a> &'a T: Add<&'a T, Output = T>,
+{
+    type Output = Polynomial<T>;
+    fn add(self, rhs: &Polynomial<T>) -> Polynomial<T> {
+        let (long, short) = match self.coefficients.len() >= rhs.coefficients.len() {
+            true => (self, rhs),
+            false => (rhs, self),
+        };
+        let mut coefficients = long.coefficients.clone();
+        for (value, other) in coefficients.iter_mut().zip(short.coefficients.iter()) {
+            *value = &*value + other;
+        }
+        return Polynomial::new(coefficients);
+    }
+}
+
+impl<T> Sub<&Polynomial<T>> for &Polynomial<T>
+where
+    T: Zero + PartialEq + Clone,
+    for<'a> &'a T: Sub<&'a T, Output = T> + Neg<Output = T>,
+{
+    type Output = Polynomial<T>;
+    fn sub(self, rhs: &Polynomial<T>) -> Polynomial<T> {
+        let length = self.coefficients.len().max(rhs.coefficients.len());
+        let coefficients = (0..length).map(|i_| match (self.coefficients.get(i_), rhs.coefficients.get(i_)) {
+            (Some(lhs), Some(rhs)) => lhs - rhs,
+            (Some(lhs), None) => lhs.clone(),
+            (None, Some(rhs)) => -rhs,
+            (None, None) => unreachable!(),
+        });
+        return Polynomial::new(coefficients.collect());
+    }
+}
+
+impl<T> Mul<&Polynomial<T>> for &Polynomial<T>
+where
+    T: Zero + PartialEq + Clone,
+    for<'a> &'a T: Add<&'a T, Output = T> + Mul<&'a T, Output = T>,
+{
+    type Output = Polynomial<T>;
+    fn mul(self, rhs: &Polynomial<T>) -> Polynomial<T> {
+        if self.is_zero() || rhs.is_zero() {
+            return Polynomial::zero();
+        }
+        let mut coefficients = vec![T::zero(); self.coefficients.len() + rhs.coefficients.len() - 1];
+        for (i_, lhs) in self.coefficients.iter().enumerate() {
+            for (j_, rhs) in rhs.coefficients.iter().enumerate() {
+                coefficients[i_ + j_] = &coefficients[i_ + j_] + &(lhs * rhs);
+            }
+        }
+        return Polynomial::new(coefficients);
+    }
+}
+
+impl<T> Neg for &Polynomial<T>
+where
+    for<'a> &'a T: Neg<Output = T>,
+{
+    type Output = Polynomial<T>;
+    fn neg(self) -> Polynomial<T> {
+        return Polynomial {
+            coefficients: self.coefficients.iter().map(|value| -value).collect(),
+        };
+    }
+}
+
+impl<T> Neg for Polynomial<T>
+where
+    for<'a> &'a T: Neg<Output = T>,
+{
+    type Output = Polynomial<T>;
+    fn neg(self) -> Polynomial<T> {
+        return -&self;
+    }
+}
+
+impl<T> Add for Polynomial<T>
+where
+    T: Zero + PartialEq + Clone,
+    for<'a> &'a T: Add<&'a T, Output = T>,
+{
+    type Output = Polynomial<T>;
+    fn add(self, rhs: Self) -> Polynomial<T> {
+        return &self + &rhs;
+    }
+}
+
+impl<T> Sub for Polynomial<T>
+where
+    T: Zero + PartialEq + Clone,
+    for<'a> &'a T: Sub<&'a T, Output = T> + Neg<Output = T>,
+{
+    type Output = Polynomial<T>;
+    fn sub(self, rhs: Self) -> Polynomial<T> {
+        return &self - &rhs;
+    }
+}
+
+impl<T> Mul for Polynomial<T>
+where
+    T: Zero + PartialEq + Clone,
+    for<'a> &'a T: Add<&'a T, Output = T> + Mul<&'a T, Output = T>,
+{
+    type Output = Polynomial<T>;
+    fn mul(self, rhs: Self) -> Polynomial<T> {
+        return &self * &rhs;
+    }
+}
+
+impl<T> Zero for Polynomial<T>
+where
+    T: Zero + PartialEq + Clone,
+    for<'a> &'a T: Add<&'a T, Output = T>,
+{
+    fn zero() -> Self {
+        return Polynomial::zero();
+    }
+}
+
+impl<T> One for Polynomial<T>
+where
+    T: Zero + One + PartialEq + Clone,
+    for<'a> &'a T: Add<&'a T, Output = T> + Mul<&'a T, Output = T>,
+{
+    fn one() -> Self {
+        return Polynomial::constant(T::one());
+    }
+}
+
+impl<T> Ring for Polynomial<T>
+where
+    T: Ring,
+    for<'a> &'a T: Add<&'a T, Output = T> + Sub<&'a T, Output = T> + Mul<&'a T, Output = T> + Neg<Output = T>,
+{
+}
+
+impl<T: fmt::Display> fmt::Display for Polynomial<T> {
+    /// `3x^2 - x + 1/2`の形式で出力する(零多項式は`0`)
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.coefficients.is_empty() {
+            return f.pad("0");
+        }
+        let mut result = String::new();
+        for (degree, value) in self.coefficients.iter().enumerate().rev() {
+            let value = value.to_string();
+            if value == "0" {
+                continue;
+            }
+            let (negative, magnitude) = match value.strip_prefix('-') {
+                Some(magnitude) => (true, magnitude),
+                None => (false, value.as_str()),
+            };
+            result += match (result.is_empty(), negative) {
+                (true, true) => "-",
+                (true, false) => "",
+                (false, true) => " - ",
+                (false, false) => " + ",
+            };
+            if magnitude != "1" || degree == 0 {
+                result += magnitude;
+            }
+            result += &match degree {
+                0 => String::new(),
+                1 => "x".to_string(),
+                _ => format!("x^{}", degree),
+            };
+        }
+        return f.pad(&result);
+    }
+}
+
+#[cfg(test)]
+mod polynomial_test {
+    use super::Polynomial;
+    use crate::math_traits::FromPrimitiveNumber;
+    use crate::num::{Integer, Rational};
+
+    fn integers(values: &[i128]) -> Polynomial<Integer> {
+        return Polynomial::new(values.iter().map(|value| Integer::from_i128(*value)).collect());
+    }
+
+    fn rationals(values: &[&str]) -> Polynomial<Rational> {
+        return Polynomial::new(values.iter().map(|value| value.parse().unwrap()).collect());
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(rationals(&["1/2", "-1", "3"]).to_string(), "3x^2 - x + 1/2");
+        assert_eq!(integers(&[0, 1, 0, -1]).to_string(), "-x^3 + x");
+        assert_eq!(integers(&[-5]).to_string(), "-5");
+        assert_eq!(integers(&[0, 0]).to_string(), "0");
+        assert_eq!(integers(&[1, 2, 0]).degree(), Some(1));
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        let a = integers(&[1, 2, 3]);
+        let b = integers(&[-1, 0, -3, 4]);
+        assert_eq!(&a + &b, integers(&[0, 2, 0, 4]));
+        assert_eq!(&a - &b, integers(&[2, 2, 6, -4]));
+        assert_eq!(&a * &b, integers(&[-1, -2, -6, -2, -1, 12]));
+        assert_eq!(&a - &a, integers(&[]));
+        assert_eq!(a.evaluate(&Integer::from_i128(-2)), Integer::from_i128(9));
+        assert_eq!(b.derivative(), integers(&[0, -6, 12]));
+        let (q, r) = b.pseudo_div_rem(&a);
+        assert_eq!(&(&q * &a) + &r, b.scale(&Integer::from_i128(9)));
+        let (q, r) = rationals(&["-1", "0", "-3", "4"]).div_rem(&rationals(&["1", "2", "3"]));
+        assert_eq!(q, rationals(&["-17/9", "4/3"]));
+        assert_eq!(r, rationals(&["8/9", "22/9"]));
+    }
+
+    #[test]
+    fn gcd_test() {
+        //(x - 1)^2 (x + 2) と (x - 1)(2x + 3)
+        let a = integers(&[2, -3, 0, 1]);
+        let b = integers(&[-3, 1, 2]);
+        assert_eq!(a.gcd(&b), integers(&[-1, 1]));
+        assert_eq!(a.scale(&Integer::from_i128(6)).gcd(&b.scale(&Integer::from_i128(4))), integers(&[-2, 2]));
+        assert_eq!(integers(&[1, 1]).gcd(&integers(&[2, 1])), integers(&[1]));
+        //Knuthの例: 係数が膨張しやすい組
+        let a = integers(&[-5, 2, 8, -3, -3, 0, 1, 0, 1]);
+        let b = integers(&[21, -9, -4, 0, 5, 0, 3]);
+        assert_eq!(a.gcd(&b), integers(&[1]));
+        let a = Polynomial::<Rational>::from(&integers(&[2, -3, 0, 1]));
+        let b = Polynomial::<Rational>::from(&integers(&[-3, 1, 2]));
+        assert_eq!(a.gcd(&b), rationals(&["-1", "1"]));
+    }
+
+    #[test]
+    fn square_free_test() {
+        //-2 (x + 1)(x - 2)^2 (x^2 + 1)^3
+        let factors = [integers(&[1, 1]), integers(&[-2, 1]), integers(&[1, 0, 1])];
+        let mut f = integers(&[-2]);
+        for (factor, multiplicity) in factors.iter().zip(1..) {
+            for _ in 0..multiplicity {
+                f = &f * factor;
+            }
+        }
+        let (content, result) = f.square_free_factorization();
+        assert_eq!(content, Integer::from_i128(-2));
+        assert_eq!(result, vec![(integers(&[1, 1]), 1), (integers(&[-2, 1]), 2), (integers(&[1, 0, 1]), 3)]);
+        let (lead, result) = rationals(&["1/3", "2/3", "1/3"]).square_free_factorization();
+        assert_eq!(lead, "1/3".parse().unwrap());
+        assert_eq!(result, vec![(rationals(&["1", "1"]), 2)]);
+    }
+
+    #[test]
+    fn resultant_test() {
+        let a = integers(&[-5, 2, 8, -3, -3, 0, 1, 0, 1]);
+        let b = integers(&[21, -9, -4, 0, 5, 0, 3]);
+        let expected: Integer = "260708".parse().unwrap();
+        assert_eq!(a.resultant(&b), expected);
+        assert_eq!(Polynomial::<Rational>::from(&a).resultant(&Polynomial::from(&b)), Rational::from(&expected));
+        assert_eq!(b.resultant(&a), expected);
+        assert_eq!(integers(&[-1, 0, 1]).resultant(&integers(&[-1, 1])), Integer::from_i128(0));
+        assert_eq!(integers(&[6, 4]).resultant(&integers(&[3, 0, 2])), Integer::from_i128(120));
+        assert_eq!(integers(&[3]).resultant(&integers(&[1, 0, 1])), Integer::from_i128(9));
+        //ax^2 + bx + c の判別式は b^2 - 4ac
+        assert_eq!(integers(&[3, -5, 2]).discriminant(), Integer::from_i128(1));
+        assert_eq!(integers(&[1, 2, 1]).discriminant(), Integer::from_i128(0));
+        //x^3 + px + q の判別式は -4p^3 - 27q^2
+        assert_eq!(integers(&[5, -2, 0, 1]).discriminant(), Integer::from_i128(-643));
+        assert_eq!(rationals(&["5", "-2", "0", "1"]).discriminant(), "-643".parse().unwrap());
+        assert_eq!(integers(&[5, -2, 0, 3]).discriminant(), Integer::from_i128(-5979));
+    }
+}
@@ -0,0 +1,318 @@
+/**多項式の実根の分離と精密化を提供するモジュールです
+
+スツルム列による根の数え上げと二分法を組み合わせ、有理数係数の多項式の相異なる実根を
+互いに交わらない有理数の閉区間に1つずつ分離します。分離した区間は二分法で任意の幅まで狭められます。
+すべて有理数の厳密な演算で行うため、浮動小数点の誤差が入り込む余地はありません。
+ */
+use crate::interval::Interval;
+use crate::math_traits::*;
+use crate::num::{Integer, Rational, Sign};
+use crate::polynomial::Polynomial;
+
+/// 有理数の符号
+fn sign_of(x: &Rational) -> Sign {
+    return x.numer().sign();
+}
+
+/// 符号の列(0は除く)の符号変化の回数
+fn count_sign_changes<I: Iterator<Item = Sign>>(signs: I) -> usize {
+    let mut changes = 0;
+    let mut previous = Sign::Zero;
+    for sign in signs.filter(|sign| *sign != Sign::Zero) {
+        if previous != Sign::Zero && sign != previous {
+            changes += 1;
+        }
+        previous = sign;
+    }
+    return changes;
+}
+
+/// 点xにおけるスツルム列の符号変化の回数
+fn sign_changes_at(sequence: &[Polynomial<Rational>], x: &Rational) -> usize {
+    return count_sign_changes(sequence.iter().map(|polynomial| sign_of(&polynomial.evaluate(x))));
+}
+
+/// 正の無限大(negative = trueの場合は負の無限大)におけるスツルム列の符号変化の回数
+fn sign_changes_at_infinity(sequence: &[Polynomial<Rational>], negative: bool) -> usize {
+    return count_sign_changes(sequence.iter().map(|polynomial| {
+        let sign = sign_of(polynomial.leading_coefficient().unwrap());
+        match negative && polynomial.degree().unwrap() % 2 == 1 {
+            true => -sign,
+            false => sign,
+        }
+    }));
+}
+
+impl Polynomial<Rational> {
+    /// 重根を除いた多項式 self / gcd(self, self') を求める
+    fn square_free_part(&self) -> Polynomial<Rational> {
+        let common = self.gcd(&self.derivative());
+        return self.div_rem(&common).0;
+    }
+
+    /**
+    スツルム列を求める
+
+    重根を除いた多項式pについて、p, p', -rem(p, p'), ... と剰余の符号を反転させながら続けた列です。
+    # Panics
+    零多項式の場合
+     */
+    pub fn sturm_sequence(&self) -> Vec<Polynomial<Rational>> {
+        assert!(!self.is_zero(), "zero polynomial has no Sturm sequence");
+        let first = self.square_free_part();
+        let second = first.derivative();
+        let mut sequence = vec![first];
+        if second.is_zero() {
+            return sequence;
+        }
+        sequence.push(second);
+        loop {
+            let length = sequence.len();
+            let remain = sequence[length - 2].div_rem(&sequence[length - 1]).1;
+            if remain.is_zero() {
+                return sequence;
+            }
+            sequence.push(-remain);
+        }
+    }
+
+    /// 相異なる実根の個数を求める
+    /// # Panics
+    /// 零多項式の場合
+    pub fn count_real_roots(&self) -> usize {
+        let sequence = self.sturm_sequence();
+        return sign_changes_at_infinity(&sequence, true) - sign_changes_at_infinity(&sequence, false);
+    }
+
+    /// 閉区間に含まれる相異なる実根の個数を求める
+    /// # Panics
+    /// 零多項式の場合
+    pub fn count_real_roots_in(&self, interval: &Interval) -> usize {
+        let sequence = self.sturm_sequence();
+        //スツルムの定理で数えられるのは半開区間(lower, upper]の根
+        let lower_is_root = sign_of(&sequence[0].evaluate(interval.lower())) == Sign::Zero;
+        let count = sign_changes_at(&sequence, interval.lower()) - sign_changes_at(&sequence, interval.upper());
+        return count + lower_is_root as usize;
+    }
+
+    /**
+    相異なる実根を1つずつ含む区間に分離する
+
+    藤原の上界で全ての根を含む区間から始め、スツルム列で根の個数を数えながら二分していきます。
+    # Returns
+    昇順に並んだ互いに交わらない閉区間の列。各区間はちょうど1つの根を含み、
+    根が区間の端点にくるのは幅0の区間(根そのもの)の場合のみ
+    # Panics
+    零多項式の場合
+     */
+    pub fn isolate_real_roots(&self) -> Vec<Interval> {
+        let sequence = self.sturm_sequence();
+        let polynomial = &sequence[0];
+        let degree = polynomial.degree().unwrap();
+        if degree == 0 {
+            return Vec::new();
+        }
+        //藤原の上界: 全ての根の絶対値は 2 max|a_{n-k} / a_n|^(1/k) 以下
+        let lead = &polynomial.coefficients()[degree];
+        let mut root_bound = Integer::zero();
+        for (k, value) in polynomial.coefficients()[..degree].iter().rev().enumerate() {
+            let ratio = (value / lead).abs();
+            let floor = ratio.numer() / ratio.denom();
+            root_bound = std::cmp::max(root_bound, &floor.nth_root(k as u32 + 1) + &Integer::one());
+        }
+        let bound = Rational::from(&(&root_bound * &Integer::from_u64(2)));
+        let lower = -&bound;
+        let mut result = Vec::new();
+        let mut pending = vec![(
+            sign_changes_at(&sequence, &lower),
+            lower,
+            sign_changes_at(&sequence, &bound),
+            bound,
+        )];
+        while let Some((lower_changes, lower, upper_changes, upper)) = pending.pop() {
+            match lower_changes - upper_changes {
+                0 => continue,
+                1 => {
+                    //隣の区間と端点を共有しないよう、両端が内側に入るまで狭める
+                    let lower_sign = sign_of(&polynomial.evaluate(&lower));
+                    let (mut inner_lower, mut inner_upper) = (lower.clone(), upper.clone());
+                    while inner_lower == lower || inner_upper == upper {
+                        (inner_lower, inner_upper) = bisect(polynomial, inner_lower, inner_upper, lower_sign.clone());
+                    }
+                    result.push(Interval::new(&inner_lower, &inner_upper));
+                    continue;
+                }
+                _ => {}
+            }
+            let middle = split_point(polynomial, &lower, &upper);
+            let middle_changes = sign_changes_at(&sequence, &middle);
+            pending.push((lower_changes, lower, middle_changes, middle.clone()));
+            pending.push((middle_changes, middle, upper_changes, upper));
+        }
+        result.sort_by(|lhs, rhs| lhs.lower().cmp(rhs.lower()));
+        return result;
+    }
+
+    /**
+    根を1つだけ含む区間を二分法で幅width以下まで狭める
+
+    根が中点にちょうど一致した場合は、その点からなる幅0の区間を返します。
+    # Arguments
+    * interval - 根をちょうど1つ含み、端点が根でない区間(または根そのものからなる幅0の区間)
+    * width - 目標とする幅(正の値)
+    # Panics
+    widthが正でない場合、または区間が上記の条件を満たさない場合
+     */
+    pub fn refine_root(&self, interval: &Interval, width: &Rational) -> Interval {
+        assert!(sign_of(width) == Sign::Positive, "width must be positive");
+        let polynomial = self.square_free_part();
+        let lower_sign = sign_of(&polynomial.evaluate(interval.lower()));
+        if lower_sign == Sign::Zero && interval.lower() == interval.upper() {
+            return interval.clone();
+        }
+        assert!(
+            lower_sign != Sign::Zero && self.count_real_roots_in(interval) == 1,
+            "interval must isolate exactly one root"
+        );
+        let (mut lower, mut upper) = (interval.lower().clone(), interval.upper().clone());
+        while &upper - &lower > *width {
+            (lower, upper) = bisect(&polynomial, lower, upper, lower_sign.clone());
+        }
+        return Interval::new(&lower, &upper);
+    }
+}
+
+/**単根を1つだけ含む区間を二分法で半分にする
+
+重根を持たない多項式の単根の前後では符号が変わるため、下端と符号の異なる側を残します。
+# Returns
+狭めた区間の(下端, 上端)。中点が根そのものだった場合は(中点, 中点)
+ */
+fn bisect(polynomial: &Polynomial<Rational>, lower: Rational, upper: Rational, lower_sign: Sign) -> (Rational, Rational) {
+    let middle = &(&lower + &upper) / &Rational::from(2);
+    let sign = sign_of(&polynomial.evaluate(&middle));
+    if sign == Sign::Zero {
+        return (middle.clone(), middle);
+    }
+    return match sign == lower_sign {
+        true => (middle, upper),
+        false => (lower, middle),
+    };
+}
+
+/// 根でない分割点を中点の近くから探す
+fn split_point(polynomial: &Polynomial<Rational>, lower: &Rational, upper: &Rational) -> Rational {
+    let width = upper - lower;
+    //中点、1/3・2/3の点、1/4・3/4の点、…の順に試す(根は有限個なので必ず見つかる)
+    let mut denominator = 2;
+    loop {
+        for numerator in 1..denominator {
+            let point = lower + &(&width * &Rational::new(&Integer::from_u64(numerator), &Integer::from_u64(denominator)));
+            if sign_of(&polynomial.evaluate(&point)) != Sign::Zero {
+                return point;
+            }
+        }
+        denominator += 1;
+    }
+}
+
+impl Polynomial<Integer> {
+    /// 相異なる実根の個数を求める([`Polynomial::<Rational>::count_real_roots`]を参照)
+    pub fn count_real_roots(&self) -> usize {
+        return Polynomial::<Rational>::from(self).count_real_roots();
+    }
+
+    /// 相異なる実根を1つずつ含む区間に分離する([`Polynomial::<Rational>::isolate_real_roots`]を参照)
+    pub fn isolate_real_roots(&self) -> Vec<Interval> {
+        return Polynomial::<Rational>::from(self).isolate_real_roots();
+    }
+
+    /// 根を1つだけ含む区間を幅width以下まで狭める([`Polynomial::<Rational>::refine_root`]を参照)
+    pub fn refine_root(&self, interval: &Interval, width: &Rational) -> Interval {
+        return Polynomial::<Rational>::from(self).refine_root(interval, width);
+    }
+}
+
+#[cfg(test)]
+mod root_isolation_test {
+    use crate::interval::Interval;
+    use crate::math_traits::FromPrimitiveNumber;
+    use crate::num::{Integer, Rational};
+    use crate::polynomial::Polynomial;
+
+    fn integers(values: &[i128]) -> Polynomial<Integer> {
+        return Polynomial::new(values.iter().map(|value| Integer::from_i128(*value)).collect());
+    }
+
+    fn rational(s: &str) -> Rational {
+        return s.parse().unwrap();
+    }
+
+    /// 区間が昇順で互いに交わらず、それぞれちょうど1つの根を含むことを確認する
+    fn assert_isolated(polynomial: &Polynomial<Integer>, intervals: &[Interval]) {
+        let rational_polynomial = Polynomial::<Rational>::from(polynomial);
+        for pair in intervals.windows(2) {
+            assert!(pair[0].upper() < pair[1].lower(), "{} and {} overlap", pair[0], pair[1]);
+        }
+        for interval in intervals {
+            assert_eq!(rational_polynomial.count_real_roots_in(interval), 1, "{}", interval);
+        }
+    }
+
+    #[test]
+    fn isolate_test() {
+        //(x^2 - 2)(x - 1)^2 (x + 3)(x^2 + 1)
+        let f = &(&(&integers(&[-2, 0, 1]) * &integers(&[1, -2, 1])) * &integers(&[3, 1])) * &integers(&[1, 0, 1]);
+        assert_eq!(f.count_real_roots(), 4);
+        let intervals = f.isolate_real_roots();
+        assert_eq!(intervals.len(), 4);
+        assert_isolated(&f, &intervals);
+        assert!(intervals[0].contains(&rational("-3")));
+        assert!(intervals[2].contains(&rational("1")));
+        let width = rational("1/1000000");
+        let around = Interval::new(&rational("-1.414215"), &rational("-1.414212"));
+        assert!(around.contains_interval(&f.refine_root(&intervals[1], &width)));
+        let around = Interval::new(&rational("1.414212"), &rational("1.414215"));
+        assert!(around.contains_interval(&f.refine_root(&intervals[3], &width)));
+        //ウィルキンソン多項式 (x - 1)(x - 2)···(x - 20)
+        let mut wilkinson = integers(&[1]);
+        for k in 1..=20 {
+            wilkinson = &wilkinson * &integers(&[-k, 1]);
+        }
+        let intervals = wilkinson.isolate_real_roots();
+        assert_eq!(intervals.len(), 20);
+        assert_isolated(&wilkinson, &intervals);
+        for (interval, k) in intervals.iter().zip(1..) {
+            assert!(interval.contains(&Rational::from_i128(k)));
+        }
+        assert_eq!(integers(&[1, 0, 1]).isolate_real_roots(), vec![]);
+        assert_eq!(integers(&[5]).isolate_real_roots(), vec![]);
+    }
+
+    #[test]
+    fn close_roots_test() {
+        //根 1/1000 と 1/1001 が非常に近い
+        let f = &integers(&[-1, 1000]) * &integers(&[-1, 1001]);
+        let intervals = f.isolate_real_roots();
+        assert_eq!(intervals.len(), 2);
+        assert_isolated(&f, &intervals);
+        assert!(intervals[0].contains(&rational("1/1001")));
+        assert!(intervals[1].contains(&rational("1/1000")));
+    }
+
+    #[test]
+    fn refine_test() {
+        let f = integers(&[-2, 0, 1]);
+        let intervals = f.isolate_real_roots();
+        let width = rational("1/1000000000000");
+        let refined = f.refine_root(&intervals[1], &width);
+        assert!(refined.width() <= width);
+        assert!(refined.lower() * refined.lower() < rational("2"));
+        assert!(refined.upper() * refined.upper() > rational("2"));
+        assert!(refined.contains(&rational("1.41421356237309")));
+        //有理数の根は途中で中点に一致することがある
+        let f = integers(&[-3, 4]);
+        let refined = f.refine_root(&Interval::new(&rational("0"), &rational("1")), &width);
+        assert_eq!(refined, Interval::point(&rational("3/4")));
+    }
+}
@@ -0,0 +1,175 @@
+//! `serde`によるシリアライズ・デシリアライズ
+//!
+//! `serde`機能を有効にした場合のみコンパイルされます。
+//! JSONのような人間が読める形式では10進数の文字列(`"123"`、`"p/q"`)として、
+//! バイナリ形式では符号と絶対値の下位バイトからの並び(内部の`Digit`幅に依存しない)として保存します。
+use std::fmt;
+
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::{SerializeTuple, Serializer};
+use serde::{Deserialize, Serialize};
+
+use crate::num::{Integer, Rational, Sign};
+
+/// 符号を表す先頭バイト
+const SIGN_NEGATIVE: u8 = 0xff;
+const SIGN_ZERO: u8 = 0;
+const SIGN_POSITIVE: u8 = 1;
+
+/// 先頭に符号、続いて絶対値を下位バイトから並べる(上位の0は省略する)
+fn to_compact_bytes(value: &Integer) -> Vec<u8> {
+    let (sign, magnitude) = value.to_bytes_le();
+    let mut bytes = vec![match sign {
+        Sign::Negative => SIGN_NEGATIVE,
+        Sign::Zero => SIGN_ZERO,
+        Sign::Positive => SIGN_POSITIVE,
+    }];
+    if sign != Sign::Zero {
+        bytes.extend(magnitude);
+    }
+    return bytes;
+}
+
+/// `to_compact_bytes`の形式から整数を復元する
+fn from_compact_bytes<E: de::Error>(bytes: &[u8]) -> Result<Integer, E> {
+    let (sign, magnitude) = match bytes.split_first() {
+        Some((&SIGN_NEGATIVE, magnitude)) => (Sign::Negative, magnitude),
+        Some((&SIGN_ZERO, magnitude)) => (Sign::Zero, magnitude),
+        Some((&SIGN_POSITIVE, magnitude)) => (Sign::Positive, magnitude),
+        _ => return Err(E::custom("invalid sign byte")),
+    };
+    let is_zero = magnitude.iter().all(|byte| *byte == 0);
+    if is_zero != (sign == Sign::Zero) {
+        return Err(E::custom("sign does not match magnitude"));
+    }
+    return Ok(Integer::from_bytes_le(sign, magnitude));
+}
+
+impl Serialize for Integer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return serializer.collect_str(self);
+        }
+        return serializer.serialize_bytes(&to_compact_bytes(self));
+    }
+}
+
+/// Integerの読み込み用Visitor
+struct IntegerVisitor;
+
+impl<'de> Visitor<'de> for IntegerVisitor {
+    type Value = Integer;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        return formatter.write_str("a decimal integer string or sign-magnitude bytes");
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        return v.parse().map_err(E::custom);
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        return from_compact_bytes(v);
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::new();
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+        return from_compact_bytes(&bytes);
+    }
+}
+
+impl<'de> Deserialize<'de> for Integer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_str(IntegerVisitor);
+        }
+        return deserializer.deserialize_bytes(IntegerVisitor);
+    }
+}
+
+impl Serialize for Rational {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return serializer.collect_str(self);
+        }
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&self.positive)?;
+        tuple.serialize_element(&self.divider)?;
+        return tuple.end();
+    }
+}
+
+/// Rationalの読み込み用Visitor
+struct RationalVisitor;
+
+impl<'de> Visitor<'de> for RationalVisitor {
+    type Value = Rational;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        return formatter.write_str("a fraction string or a pair of integers");
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        return v.parse().map_err(E::custom);
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let positive: Integer = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let divider: Integer = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        if divider.sign() == Sign::Zero {
+            return Err(de::Error::custom("denominator must not be zero"));
+        }
+        return Ok(Rational::new(&positive, &divider));
+    }
+}
+
+impl<'de> Deserialize<'de> for Rational {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_str(RationalVisitor);
+        }
+        return deserializer.deserialize_tuple(2, RationalVisitor);
+    }
+}
+
+#[cfg(test)]
+mod serde_compat_test {
+    use crate::math_traits::FromPrimitiveNumber;
+    use crate::num::{Integer, Rational};
+
+    #[test]
+    fn json_test() {
+        let value: Integer = "-123456789012345678901234567890123456789012345".parse().unwrap();
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, "\"-123456789012345678901234567890123456789012345\"");
+        assert_eq!(serde_json::from_str::<Integer>(&json).unwrap(), value);
+
+        let ratio: Rational = "-22/7".parse().unwrap();
+        let json = serde_json::to_string(&vec![ratio.clone(), Rational::from(&Integer::from_i128(5))]).unwrap();
+        assert_eq!(json, "[\"-22/7\",\"5\"]");
+        assert_eq!(serde_json::from_str::<Vec<Rational>>(&json).unwrap()[0], ratio);
+        assert!(serde_json::from_str::<Rational>("\"1/0\"").is_err());
+    }
+
+    #[test]
+    fn binary_test() {
+        let value = Integer::from_i128(-258);
+        let bytes = bincode::serialize(&value).unwrap();
+        //長さ(u64) + 符号 + 絶対値の下位バイトから
+        assert_eq!(bytes, vec![3, 0, 0, 0, 0, 0, 0, 0, 0xff, 2, 1]);
+        assert_eq!(bincode::deserialize::<Integer>(&bytes).unwrap(), value);
+
+        for text in ["0", "1", "340282366920938463463374607431768211456", "-98765432109876543210987654321098765432109876543210"] {
+            let value: Integer = text.parse().unwrap();
+            let bytes = bincode::serialize(&value).unwrap();
+            assert_eq!(bincode::deserialize::<Integer>(&bytes).unwrap(), value);
+        }
+
+        let ratio: Rational = "-340282366920938463463374607431768211457/12".parse().unwrap();
+        let bytes = bincode::serialize(&ratio).unwrap();
+        assert_eq!(bincode::deserialize::<Rational>(&bytes).unwrap(), ratio);
+    }
+}
@@ -0,0 +1,27 @@
+//! テスト用の共通ヘルパー
+use crate::num::Digit;
+
+/// テストデータ生成用の決定的な擬似乱数(xorshift)
+pub(crate) struct XorShift {
+    state: Digit,
+}
+
+impl XorShift {
+    /// 固定のシードで作成する(同じ呼び出し順なら常に同じ値の列になる)
+    pub(crate) fn new() -> XorShift {
+        return XorShift { state: 0x2545F491 };
+    }
+
+    /// 次の1桁を返す
+    pub(crate) fn next_digit(&mut self) -> Digit {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        return self.state;
+    }
+
+    /// 擬似乱数で埋めたlen桁の配列を返す
+    pub(crate) fn digits(&mut self, len: usize) -> Vec<Digit> {
+        return (0..len).map(|_| self.next_digit()).collect();
+    }
+}